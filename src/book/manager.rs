@@ -0,0 +1,302 @@
+//! Layer several Polyglot books into one prioritized, weighted book.
+//!
+//! A common setup is a small hand-tuned main book backed by a large general
+//! book for positions the main book doesn't cover. `BookManager` holds an
+//! ordered list of books with per-book weight multipliers and probes them in
+//! one of two modes:
+//! - [`ProbeMode::StrictFallback`]: return the first book (in priority order)
+//!   that has any entry for the position, ignoring the rest.
+//! - [`ProbeMode::Blend`]: merge entries across every book that has one for
+//!   the position, scaling each book's raw weights by its multiplier first.
+//!
+//! A `min_weight` floor drops near-zero book moves (e.g. rarely-played lines
+//! recorded with a single occurrence) from consideration in either mode.
+
+use super::polyglot::{PolyglotBook, DEFAULT_TEMPERATURE};
+use super::rng::Rng;
+use chess::{Board, ChessMove};
+
+/// How `BookManager::probe` combines results from multiple books.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeMode {
+    /// Return the first book in priority order that has any entry for the
+    /// position; later books are only consulted if earlier ones are silent.
+    StrictFallback,
+    /// Merge entries from every book that has one for the position, each
+    /// scaled by that book's weight multiplier.
+    Blend,
+}
+
+/// A move and its combined weight after scaling and (in blend mode) merging
+/// across books. Not tied to any single book's `u16` weight, since a blended
+/// weight can exceed that range.
+#[derive(Debug, Clone, Copy)]
+pub struct ScoredMove {
+    pub mv: ChessMove,
+    pub weight: f64,
+}
+
+struct WeightedBook {
+    book: PolyglotBook,
+    weight_multiplier: f32,
+}
+
+impl WeightedBook {
+    fn scored_moves(&self, board: &Board, min_weight: u16) -> Vec<ScoredMove> {
+        self.book
+            .probe(board)
+            .into_iter()
+            .filter(|e| e.weight >= min_weight)
+            .filter_map(|e| {
+                e.to_chess_move(board).map(|mv| ScoredMove {
+                    mv,
+                    weight: e.weight as f64 * self.weight_multiplier as f64,
+                })
+            })
+            .collect()
+    }
+}
+
+/// A prioritized, weighted stack of Polyglot books probed as one.
+pub struct BookManager {
+    books: Vec<WeightedBook>,
+    mode: ProbeMode,
+    min_weight: u16,
+}
+
+impl BookManager {
+    pub fn new() -> Self {
+        Self {
+            books: Vec::new(),
+            mode: ProbeMode::StrictFallback,
+            min_weight: 0,
+        }
+    }
+
+    /// Add a book at the next (lowest) priority, with `weight_multiplier`
+    /// applied to its raw entry weights. In [`ProbeMode::StrictFallback`] it
+    /// only scales the returned weights; which book answers depends on
+    /// presence/absence alone.
+    pub fn add_book(mut self, book: PolyglotBook, weight_multiplier: f32) -> Self {
+        self.books.push(WeightedBook { book, weight_multiplier });
+        self
+    }
+
+    pub fn with_mode(mut self, mode: ProbeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Drop entries with a raw book weight below `min_weight` before they're
+    /// considered for fallback or blending.
+    pub fn with_min_weight(mut self, min_weight: u16) -> Self {
+        self.min_weight = min_weight;
+        self
+    }
+
+    /// Get the combined, weighted moves for a position under the configured
+    /// [`ProbeMode`].
+    pub fn probe(&self, board: &Board) -> Vec<ScoredMove> {
+        match self.mode {
+            ProbeMode::StrictFallback => {
+                for book in &self.books {
+                    let moves = book.scored_moves(board, self.min_weight);
+                    if !moves.is_empty() {
+                        return moves;
+                    }
+                }
+                Vec::new()
+            }
+            ProbeMode::Blend => {
+                let mut merged: Vec<ScoredMove> = Vec::new();
+                for book in &self.books {
+                    for scored in book.scored_moves(board, self.min_weight) {
+                        match merged.iter_mut().find(|m| m.mv == scored.mv) {
+                            Some(existing) => existing.weight += scored.weight,
+                            None => merged.push(scored),
+                        }
+                    }
+                }
+                merged
+            }
+        }
+    }
+
+    /// The highest-weighted move across the combined probe result.
+    pub fn probe_best_move(&self, board: &Board) -> Option<ChessMove> {
+        self.probe(board)
+            .into_iter()
+            .max_by(|a, b| a.weight.total_cmp(&b.weight))
+            .map(|s| s.mv)
+    }
+
+    /// A weighted random move from the combined probe result, sampled from
+    /// `rng` with `temperature` (see `PolyglotBook::probe_move_with_rng`).
+    pub fn probe_move_with_rng(&self, board: &Board, rng: &mut Rng, temperature: f32) -> Option<ChessMove> {
+        let scored = self.probe(board);
+        if scored.is_empty() {
+            return None;
+        }
+        if temperature <= 0.0 {
+            return scored
+                .iter()
+                .max_by(|a, b| a.weight.total_cmp(&b.weight))
+                .map(|s| s.mv);
+        }
+
+        let scaled_weights: Vec<f64> = scored
+            .iter()
+            .map(|s| s.weight.max(0.0).powf(1.0 / temperature as f64))
+            .collect();
+        let total_weight: f64 = scaled_weights.iter().sum();
+
+        if total_weight <= 0.0 {
+            return scored.first().map(|s| s.mv);
+        }
+
+        let sample = rng.next_f64() * total_weight;
+        let mut cumulative = 0.0;
+        for (s, weight) in scored.iter().zip(&scaled_weights) {
+            cumulative += weight;
+            if sample < cumulative {
+                return Some(s.mv);
+            }
+        }
+        scored.last().map(|s| s.mv)
+    }
+
+    /// Get a weighted random move, seeding a fresh RNG from the clock each
+    /// call (see `PolyglotBook::probe_move`'s same caveat about tight loops).
+    pub fn probe_move(&self, board: &Board) -> Option<ChessMove> {
+        let mut rng = Rng::from_entropy();
+        self.probe_move_with_rng(board, &mut rng, DEFAULT_TEMPERATURE)
+    }
+}
+
+impl Default for BookManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::book::BookWriter;
+
+    fn book_with(board: &Board, mv: ChessMove, weight: u16) -> PolyglotBook {
+        let mut writer = BookWriter::new();
+        for _ in 0..weight {
+            writer.add_position(board, mv);
+        }
+        let path = std::env::temp_dir().join(format!(
+            "chessgo-test-manager-book-{:?}-{}.bin",
+            std::thread::current().id(),
+            weight,
+        ));
+        writer.write(&path).unwrap();
+        let book = PolyglotBook::load(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        book
+    }
+
+    #[test]
+    fn test_strict_fallback_prefers_first_book_with_any_entry() {
+        let board = Board::default();
+        let e4 = ChessMove::new(chess::Square::E2, chess::Square::E4, None);
+        let d4 = ChessMove::new(chess::Square::D2, chess::Square::D4, None);
+
+        let main_book = book_with(&board, e4, 1);
+        let general_book = book_with(&board, d4, 1000);
+
+        let manager = BookManager::new().add_book(main_book, 1.0).add_book(general_book, 1.0);
+
+        assert_eq!(manager.probe_best_move(&board), Some(e4));
+    }
+
+    #[test]
+    fn test_strict_fallback_falls_through_to_next_book_when_first_is_silent() {
+        let board = Board::default();
+        let e4 = ChessMove::new(chess::Square::E2, chess::Square::E4, None);
+        let after_e4 = board.make_move_new(e4);
+        let e5 = ChessMove::new(chess::Square::E7, chess::Square::E5, None);
+
+        let main_book = book_with(&board, e4, 1);
+        let general_book = book_with(&after_e4, e5, 1);
+
+        let manager = BookManager::new().add_book(main_book, 1.0).add_book(general_book, 1.0);
+
+        assert_eq!(manager.probe_best_move(&after_e4), Some(e5));
+    }
+
+    #[test]
+    fn test_blend_mode_merges_weights_across_books() {
+        let board = Board::default();
+        let e4 = ChessMove::new(chess::Square::E2, chess::Square::E4, None);
+        let d4 = ChessMove::new(chess::Square::D2, chess::Square::D4, None);
+
+        let book_a = book_with(&board, e4, 10);
+        let book_b = book_with(&board, e4, 5);
+        let book_c = book_with(&board, d4, 100);
+
+        let manager = BookManager::new()
+            .with_mode(ProbeMode::Blend)
+            .add_book(book_a, 1.0)
+            .add_book(book_b, 1.0)
+            .add_book(book_c, 1.0);
+
+        let scored = manager.probe(&board);
+        let e4_weight: f64 = scored.iter().find(|s| s.mv == e4).unwrap().weight;
+        assert_eq!(e4_weight, 15.0);
+        // d4's book is weighted much heavier, so it should still win overall.
+        assert_eq!(manager.probe_best_move(&board), Some(d4));
+    }
+
+    #[test]
+    fn test_weight_multiplier_scales_a_books_contribution_in_blend_mode() {
+        let board = Board::default();
+        let e4 = ChessMove::new(chess::Square::E2, chess::Square::E4, None);
+
+        let book_a = book_with(&board, e4, 10);
+
+        let manager = BookManager::new()
+            .with_mode(ProbeMode::Blend)
+            .add_book(book_a, 0.5);
+
+        let scored = manager.probe(&board);
+        assert_eq!(scored[0].weight, 5.0);
+    }
+
+    #[test]
+    fn test_min_weight_floor_drops_near_zero_moves() {
+        let board = Board::default();
+        let e4 = ChessMove::new(chess::Square::E2, chess::Square::E4, None);
+        let d4 = ChessMove::new(chess::Square::D2, chess::Square::D4, None);
+
+        let book_a = book_with(&board, e4, 1);
+        let book_b = book_with(&board, d4, 100);
+
+        let manager = BookManager::new()
+            .with_mode(ProbeMode::Blend)
+            .with_min_weight(2)
+            .add_book(book_a, 1.0)
+            .add_book(book_b, 1.0);
+
+        let scored = manager.probe(&board);
+        assert_eq!(scored.len(), 1);
+        assert_eq!(scored[0].mv, d4);
+    }
+
+    #[test]
+    fn test_probe_returns_empty_when_no_book_has_the_position() {
+        let board = Board::default();
+        let e4 = ChessMove::new(chess::Square::E2, chess::Square::E4, None);
+        let after_e4 = board.make_move_new(e4);
+        let book_a = book_with(&board, e4, 1);
+
+        let manager = BookManager::new().add_book(book_a, 1.0);
+
+        assert!(manager.probe(&after_e4).is_empty());
+        assert_eq!(manager.probe_best_move(&after_e4), None);
+    }
+}
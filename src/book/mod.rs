@@ -17,8 +17,16 @@
 //! }
 //! ```
 
+mod manager;
+mod pgn;
 mod polyglot;
+mod rng;
+mod writer;
 mod zobrist;
 
-pub use polyglot::{PolyglotBook, BookEntry};
+pub use manager::{BookManager, ProbeMode, ScoredMove};
+pub use pgn::{parse_pgn_games, PgnGame};
+pub use polyglot::{PolyglotBook, BookEntry, GameResult};
+pub use rng::Rng;
+pub use writer::{BookWriter, ResultBias};
 pub use zobrist::polyglot_hash;
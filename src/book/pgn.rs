@@ -0,0 +1,278 @@
+//! Minimal PGN reader: turns movetext into `ChessMove`s so `BookWriter` can
+//! learn a book straight from a game archive instead of only from moves the
+//! engine played itself.
+//!
+//! Only what `BookWriter::add_pgn` needs is implemented: tag pairs are
+//! scanned for `Result`, and movetext is stripped of move numbers, `{...}`
+//! comments, and NAGs before each remaining token is resolved as a SAN move
+//! against the running board. Anything else (variations, non-UTF8 files,
+//! the `SetUp`/`FEN` tag for a non-standard start) isn't handled; a token
+//! that fails to resolve just ends that game's move list early rather than
+//! panicking or corrupting the rest of the batch.
+
+use super::polyglot::GameResult;
+use chess::{Board, ChessMove, File, Piece, Rank, Square};
+use std::str::FromStr;
+
+/// One parsed game: the moves played from the standard starting position,
+/// and its result if the `Result` tag (or a trailing result token in the
+/// movetext) could be read.
+#[derive(Debug, Clone)]
+pub struct PgnGame {
+    pub moves: Vec<ChessMove>,
+    pub result: Option<GameResult>,
+}
+
+/// Parse every game in `pgn`, skipping ones with no resolvable moves.
+pub fn parse_pgn_games(pgn: &str) -> Vec<PgnGame> {
+    let mut games = Vec::new();
+    let mut tags = String::new();
+    let mut movetext = String::new();
+    let mut in_movetext = false;
+
+    for line in pgn.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            if in_movetext {
+                if let Some(game) = parse_single_game(&tags, &movetext) {
+                    games.push(game);
+                }
+                tags.clear();
+                movetext.clear();
+                in_movetext = false;
+            }
+            tags.push_str(trimmed);
+            tags.push('\n');
+        } else if !trimmed.is_empty() {
+            in_movetext = true;
+            movetext.push(' ');
+            movetext.push_str(trimmed);
+        }
+    }
+    if in_movetext {
+        if let Some(game) = parse_single_game(&tags, &movetext) {
+            games.push(game);
+        }
+    }
+    games
+}
+
+fn parse_single_game(tags: &str, movetext: &str) -> Option<PgnGame> {
+    let mut result = extract_result_tag(tags);
+    let cleaned = strip_comments(movetext);
+    let mut board = Board::default();
+    let mut moves = Vec::new();
+
+    for raw_tok in cleaned.split_whitespace() {
+        if raw_tok.starts_with('$') {
+            continue;
+        }
+        let tok = strip_move_number(raw_tok);
+        if tok.is_empty() {
+            continue;
+        }
+        if let Some(r) = parse_result_token(tok) {
+            result = result.or(r);
+            continue;
+        }
+        match parse_san_move(&board, tok) {
+            Some(mv) => {
+                board = board.make_move_new(mv);
+                moves.push(mv);
+            }
+            None => break,
+        }
+    }
+
+    if moves.is_empty() {
+        None
+    } else {
+        Some(PgnGame { moves, result })
+    }
+}
+
+/// Drop `{...}` comments (PGN comments don't nest).
+fn strip_comments(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_comment = false;
+    for c in text.chars() {
+        match c {
+            '{' => in_comment = true,
+            '}' => in_comment = false,
+            _ if !in_comment => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Strip a leading move-number marker like `12.` or `12...` off a token.
+fn strip_move_number(tok: &str) -> &str {
+    let digits_end = tok.find(|c: char| !c.is_ascii_digit()).unwrap_or(tok.len());
+    if digits_end == 0 || tok.as_bytes().get(digits_end) != Some(&b'.') {
+        return tok;
+    }
+    tok[digits_end..].trim_start_matches('.')
+}
+
+fn parse_result_token(tok: &str) -> Option<Option<GameResult>> {
+    match tok {
+        "1-0" => Some(Some(GameResult::WhiteWin)),
+        "0-1" => Some(Some(GameResult::BlackWin)),
+        "1/2-1/2" => Some(Some(GameResult::Draw)),
+        "*" => Some(None),
+        _ => None,
+    }
+}
+
+fn extract_result_tag(tags: &str) -> Option<GameResult> {
+    for line in tags.lines() {
+        if let Some(rest) = line.trim().strip_prefix("[Result \"") {
+            let value = rest.trim_end_matches("\"]");
+            return parse_result_token(value).flatten();
+        }
+    }
+    None
+}
+
+/// Resolve one SAN token (e.g. `Nf3`, `exd5`, `O-O`, `e8=Q+`) to the legal
+/// move it names in `board`, by filtering the legal move list down to the
+/// piece/destination/disambiguation/promotion the token specifies. Castling
+/// is matched directly since Polyglot's king-captures-rook convention
+/// doesn't apply to SAN.
+fn parse_san_move(board: &Board, token: &str) -> Option<ChessMove> {
+    let san = token.trim_end_matches(['+', '#', '!', '?']);
+
+    if san == "O-O" || san == "0-0" {
+        return chess::MoveGen::new_legal(board).find(|m| {
+            board.piece_on(m.get_source()) == Some(Piece::King)
+                && m.get_source().get_file() == File::E
+                && m.get_dest().get_file() == File::G
+        });
+    }
+    if san == "O-O-O" || san == "0-0-0" {
+        return chess::MoveGen::new_legal(board).find(|m| {
+            board.piece_on(m.get_source()) == Some(Piece::King)
+                && m.get_source().get_file() == File::E
+                && m.get_dest().get_file() == File::C
+        });
+    }
+
+    let (body, promotion) = match san.split_once('=') {
+        Some((base, promo)) => (base, match promo.chars().next() {
+            Some('Q') => Some(Piece::Queen),
+            Some('R') => Some(Piece::Rook),
+            Some('B') => Some(Piece::Bishop),
+            Some('N') => Some(Piece::Knight),
+            _ => None,
+        }),
+        None => (san, None),
+    };
+
+    let piece = match body.as_bytes().first()? {
+        b'N' => Piece::Knight,
+        b'B' => Piece::Bishop,
+        b'R' => Piece::Rook,
+        b'Q' => Piece::Queen,
+        b'K' => Piece::King,
+        _ => Piece::Pawn,
+    };
+    let after_piece = if piece == Piece::Pawn { body } else { &body[1..] };
+    let squares: String = after_piece.chars().filter(|&c| c != 'x').collect();
+    if squares.len() < 2 {
+        return None;
+    }
+    let dest = Square::from_str(&squares[squares.len() - 2..]).ok()?;
+    let disambig = &squares[..squares.len() - 2];
+
+    let source_matches = |sq: Square| -> bool {
+        match disambig.len() {
+            0 => true,
+            1 => {
+                let c = disambig.chars().next().unwrap();
+                if c.is_ascii_digit() {
+                    sq.get_rank() == Rank::from_index((c as u8 - b'1') as usize)
+                } else {
+                    sq.get_file() == File::from_index((c as u8 - b'a') as usize)
+                }
+            }
+            _ => Square::from_str(disambig).map(|s| s == sq).unwrap_or(false),
+        }
+    };
+
+    chess::MoveGen::new_legal(board).find(|m| {
+        board.piece_on(m.get_source()) == Some(piece)
+            && m.get_dest() == dest
+            && m.get_promotion() == promotion
+            && source_matches(m.get_source())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_san_move_pawn_push_and_capture() {
+        let board = Board::default();
+        let e4 = parse_san_move(&board, "e4").unwrap();
+        assert_eq!(e4, ChessMove::new(Square::E2, Square::E4, None));
+
+        let after_e4 = board.make_move_new(e4);
+        let d5 = parse_san_move(&after_e4, "d5").unwrap();
+        let after_d5 = after_e4.make_move_new(d5);
+
+        let exd5 = parse_san_move(&after_d5, "exd5").unwrap();
+        assert_eq!(exd5, ChessMove::new(Square::E4, Square::D5, None));
+    }
+
+    #[test]
+    fn test_parse_san_move_disambiguates_by_file() {
+        // Knights on b3 and f3 both reach d2; the file letter in "Nbd2" /
+        // "Nfd2" must pick between them.
+        let board = Board::from_str("4k3/8/8/8/8/1N3N2/8/4K3 w - - 0 1").unwrap();
+        let from_b = parse_san_move(&board, "Nbd2").unwrap();
+        assert_eq!(from_b.get_source(), Square::B3);
+        let from_f = parse_san_move(&board, "Nfd2").unwrap();
+        assert_eq!(from_f.get_source(), Square::F3);
+    }
+
+    #[test]
+    fn test_parse_san_move_castling_and_promotion() {
+        let board = Board::from_str("4k3/P7/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        let castle = parse_san_move(&board, "O-O").unwrap();
+        assert_eq!(castle, ChessMove::new(Square::E1, Square::G1, None));
+
+        let promo = parse_san_move(&board, "a8=Q").unwrap();
+        assert_eq!(promo, ChessMove::new(Square::A7, Square::A8, Some(Piece::Queen)));
+    }
+
+    #[test]
+    fn test_parse_pgn_games_splits_games_and_reads_result() {
+        let pgn = "\
+[Event \"Test\"]
+[Result \"1-0\"]
+
+1. e4 e5 2. Nf3 Nc6 1-0
+
+[Event \"Test 2\"]
+[Result \"0-1\"]
+
+1. d4 d5 {a comment} 2. c4 $1 e6 0-1
+";
+        let games = parse_pgn_games(pgn);
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].moves.len(), 4);
+        assert_eq!(games[0].result, Some(GameResult::WhiteWin));
+        assert_eq!(games[1].moves.len(), 4);
+        assert_eq!(games[1].result, Some(GameResult::BlackWin));
+    }
+
+    #[test]
+    fn test_parse_pgn_games_stops_game_at_first_unresolvable_token() {
+        let pgn = "[Event \"Test\"]\n\n1. e4 e5 2. Zz9 Nc6 1-0\n";
+        let games = parse_pgn_games(pgn);
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].moves.len(), 2);
+    }
+}
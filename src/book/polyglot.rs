@@ -3,15 +3,53 @@
 //! This module implements reading and probing of Polyglot format (.bin) opening books.
 //! The format consists of 16-byte entries sorted by position hash.
 
+use super::rng::Rng;
+use super::writer::encode_move;
 use super::zobrist::polyglot_hash;
-use chess::{Board, Square, Piece, File, Rank};
+use chess::{Board, ChessMove, Color, Square, Piece, File, Rank};
+use memmap2::Mmap;
 use std::fs::File as FsFile;
-use std::io::{self, Read, Seek, SeekFrom};
+use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::path::Path;
 
+/// Temperature of 1.0 samples proportional to raw book weight (the
+/// historical `probe_move` behavior).
+pub const DEFAULT_TEMPERATURE: f32 = 1.0;
+
 /// Size of a single Polyglot entry in bytes
 const ENTRY_SIZE: usize = 16;
 
+/// Outcome of a finished game, for `PolyglotBook::record_result`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameResult {
+    WhiteWin,
+    Draw,
+    BlackWin,
+}
+
+impl GameResult {
+    /// Score from `mover`'s perspective: 0 = loss, 1 = draw, 2 = win. The
+    /// fixed-point unit `record_result` accumulates into an entry's `learn`.
+    fn score_for(self, mover: Color) -> u32 {
+        match (self, mover) {
+            (GameResult::Draw, _) => 1,
+            (GameResult::WhiteWin, Color::White) | (GameResult::BlackWin, Color::Black) => 2,
+            _ => 0,
+        }
+    }
+}
+
+/// Unpack a `BookEntry::learn` value into (games recorded, cumulative
+/// outcome score), the two halves `pack_learn` packed in.
+fn unpack_learn(learn: u32) -> (u16, u16) {
+    ((learn >> 16) as u16, learn as u16)
+}
+
+/// Pack (games recorded, cumulative outcome score) into a `learn` value.
+fn pack_learn(games: u16, score: u16) -> u32 {
+    ((games as u32) << 16) | (score as u32)
+}
+
 /// A single entry from a Polyglot opening book
 #[derive(Debug, Clone, Copy)]
 pub struct BookEntry {
@@ -26,6 +64,14 @@ pub struct BookEntry {
 }
 
 impl BookEntry {
+    /// Build an entry from its already-encoded fields (see
+    /// `crate::book::BookWriter`, which computes `key`/`raw_move` for a
+    /// played move). `learn` starts at 0; `PolyglotBook::record_result`
+    /// fills it in later.
+    pub(crate) fn new(key: u64, raw_move: u16, weight: u16) -> Self {
+        Self { key, raw_move, weight, learn: 0 }
+    }
+
     /// Parse an entry from raw bytes (big-endian format)
     fn from_bytes(bytes: &[u8; 16]) -> Self {
         Self {
@@ -39,6 +85,17 @@ impl BookEntry {
         }
     }
 
+    /// Serialize to the 16-byte big-endian Polyglot record format (the
+    /// inverse of `from_bytes`), for `crate::book::BookWriter`.
+    pub(crate) fn to_bytes(&self) -> [u8; 16] {
+        let mut bytes = [0u8; 16];
+        bytes[0..8].copy_from_slice(&self.key.to_be_bytes());
+        bytes[8..10].copy_from_slice(&self.raw_move.to_be_bytes());
+        bytes[10..12].copy_from_slice(&self.weight.to_be_bytes());
+        bytes[12..16].copy_from_slice(&self.learn.to_be_bytes());
+        bytes
+    }
+
     /// Decode the raw move to source square, destination square, and promotion
     pub fn decode_move(&self) -> (Square, Square, Option<Piece>) {
         let to_file = (self.raw_move & 0x7) as usize;
@@ -131,8 +188,10 @@ pub struct PolyglotBook {
 enum BookData {
     /// Entries stored in memory
     Memory(Vec<BookEntry>),
-    /// File-based access (for large books)
-    File { path: String },
+    /// Large books: the file is memory-mapped once at `load` and probed by
+    /// binary search directly over the mapped bytes, with no syscalls (and
+    /// no repeated `FsFile::open`) per probe.
+    Mapped(Mmap),
 }
 
 impl PolyglotBook {
@@ -155,7 +214,12 @@ impl PolyglotBook {
         let entry_count = (file_size / ENTRY_SIZE as u64) as usize;
         let desc = path.to_string_lossy().to_string();
         
-        // For books under 50MB, load into memory for faster access
+        // Small books are copied into a `Vec` (avoids a page fault per cold
+        // probe); larger ones are memory-mapped instead of copied, since the
+        // mapped path below is probed just as fast and doesn't pay the
+        // up-front copy. This threshold is only a memory/startup-time
+        // tradeoff now, not a performance cliff, and could be lowered or
+        // removed without probing getting any slower.
         const MEMORY_THRESHOLD: u64 = 50 * 1024 * 1024;
         
         if file_size <= MEMORY_THRESHOLD {
@@ -176,8 +240,14 @@ impl PolyglotBook {
                 desc,
             })
         } else {
+            // SAFETY: requires the backing file isn't modified out from under
+            // the mapping for as long as this `PolyglotBook` lives; we never
+            // write to a file-backed book in place (`record_result`/`save`
+            // only support in-memory books), so that's on whoever else holds
+            // the file open.
+            let mmap = unsafe { Mmap::map(&file)? };
             Ok(Self {
-                data: BookData::File { path: desc.clone() },
+                data: BookData::Mapped(mmap),
                 entry_count,
                 desc,
             })
@@ -190,39 +260,61 @@ impl PolyglotBook {
         self.find_entries(key)
     }
 
-    /// Get a weighted random move from the book for a position
+    /// Get a weighted random move from the book for a position, seeding a
+    /// fresh RNG from the clock each call. Prefer `probe_move_with_rng` for
+    /// reproducible games or tests, since a clock-seeded RNG like this one
+    /// degenerates when called in a tight loop.
     pub fn probe_move(&self, board: &Board) -> Option<chess::ChessMove> {
+        let mut rng = Rng::from_entropy();
+        self.probe_move_with_rng(board, &mut rng, DEFAULT_TEMPERATURE)
+    }
+
+    /// Get a move from the book, sampled from `rng` with `temperature`
+    /// controlling how sharply selection favors the heaviest-weighted move:
+    /// - `temperature` -> 0: collapses to `probe_best_move` (deterministic).
+    /// - `temperature` == 1.0: sample proportional to raw `weight` (the
+    ///   historical `probe_move` behavior).
+    /// - `temperature` > 1.0: flattens the distribution toward uniform.
+    pub fn probe_move_with_rng(
+        &self,
+        board: &Board,
+        rng: &mut Rng,
+        temperature: f32,
+    ) -> Option<chess::ChessMove> {
         let entries = self.probe(board);
         if entries.is_empty() {
             return None;
         }
 
-        // Calculate total weight
-        let total_weight: u32 = entries.iter().map(|e| e.weight as u32).sum();
-        
-        if total_weight == 0 {
-            // If all weights are 0, just pick the first entry
+        if temperature <= 0.0 {
+            return self.probe_best_move(board);
+        }
+
+        // Sample proportional to weight^(1/temperature); done in f64 since
+        // `powf` with a large inverse temperature can overflow u32 weights.
+        let scaled_weights: Vec<f64> = entries
+            .iter()
+            .map(|e| (e.weight as f64).max(0.0).powf(1.0 / temperature as f64))
+            .collect();
+        let total_weight: f64 = scaled_weights.iter().sum();
+
+        if total_weight <= 0.0 {
+            // If every entry has zero weight, just pick the first.
             return entries[0].to_chess_move(board);
         }
 
-        // Simple weighted random selection using a basic LCG
-        // This avoids needing the rand crate
-        let seed = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_nanos() as u64)
-            .unwrap_or(12345);
-        let random = (seed.wrapping_mul(6364136223846793005).wrapping_add(1)) % total_weight as u64;
-        
-        let mut cumulative = 0u64;
-        for entry in &entries {
-            cumulative += entry.weight as u64;
-            if random < cumulative {
+        let sample = rng.next_f64() * total_weight;
+        let mut cumulative = 0.0;
+        for (entry, weight) in entries.iter().zip(&scaled_weights) {
+            cumulative += weight;
+            if sample < cumulative {
                 return entry.to_chess_move(board);
             }
         }
-        
-        // Fallback to first entry
-        entries[0].to_chess_move(board)
+
+        // Floating-point rounding can leave `sample` just past the last
+        // cumulative boundary; fall back to the last entry rather than None.
+        entries.last().and_then(|e| e.to_chess_move(board))
     }
 
     /// Get the best move (highest weight) from the book
@@ -234,11 +326,81 @@ impl PolyglotBook {
             .and_then(|e| e.to_chess_move(board))
     }
 
+    /// Record how a game finished, updating `learn`/`weight` for every book
+    /// entry whose move was actually played along `moves` (walked from
+    /// `start`), so the book deprioritizes lines that repeatedly lose and
+    /// favors proven ones across sessions. Only supported once the book is
+    /// loaded into memory (see the `load` size threshold) — file-backed
+    /// books are read-only here, since rewriting a large file in place
+    /// isn't attempted; call is a no-op on those.
+    pub fn record_result(&mut self, start: Board, moves: &[ChessMove], result: GameResult) {
+        self.record_result_with_step(start, moves, result, 1);
+    }
+
+    /// Same as `record_result`, but `step` controls how much a clear win or
+    /// loss nudges `weight` per move (a draw never changes weight either
+    /// way) — `record_result` is just this with `step` fixed at 1. Useful
+    /// for tuning how aggressively the book should react to a single game's
+    /// outcome versus the number of games it takes to shift a line's weight.
+    pub fn record_result_with_step(&mut self, start: Board, moves: &[ChessMove], result: GameResult, step: u16) {
+        let BookData::Memory(entries) = &mut self.data else {
+            return;
+        };
+        let mut board = start;
+        for &mv in moves {
+            let key = polyglot_hash(&board);
+            let raw_move = encode_move(&board, mv);
+            let mover = board.side_to_move();
+            if let Some(entry) = entries.iter_mut().find(|e| e.key == key && e.raw_move == raw_move) {
+                let (games, score) = unpack_learn(entry.learn);
+                entry.learn = pack_learn(
+                    games.saturating_add(1),
+                    score.saturating_add(result.score_for(mover) as u16),
+                );
+                // Nudge weight toward the long-run outcome: a clear win
+                // raises it, a clear loss lowers it, a draw leaves it alone.
+                match result.score_for(mover) {
+                    2 => entry.weight = entry.weight.saturating_add(step),
+                    0 => entry.weight = entry.weight.saturating_sub(step),
+                    _ => {}
+                }
+            }
+            board = board.make_move_new(mv);
+        }
+    }
+
+    /// Rewrite this book to `path` as 16-byte big-endian records, re-sorted
+    /// the same way `BookWriter::finalize` sorts (ascending key, ties by
+    /// descending weight) so `record_result`'s weight updates don't desync
+    /// `find_entries_memory`'s binary search. Only supported for in-memory
+    /// books (see `record_result`).
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let BookData::Memory(entries) = &self.data else {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "save is only supported for in-memory books",
+            ));
+        };
+        let mut sorted = entries.clone();
+        sorted.sort_by(|a, b| a.key.cmp(&b.key).then(b.weight.cmp(&a.weight)));
+
+        let mut file = FsFile::create(path)?;
+        for entry in &sorted {
+            file.write_all(&entry.to_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Save back to the path this book was loaded from (see `desc`).
+    pub fn flush(&self) -> io::Result<()> {
+        self.save(&self.desc)
+    }
+
     /// Find all entries matching a key using binary search
     fn find_entries(&self, key: u64) -> Vec<BookEntry> {
         match &self.data {
             BookData::Memory(entries) => self.find_entries_memory(entries, key),
-            BookData::File { path } => self.find_entries_file(path, key).unwrap_or_default(),
+            BookData::Mapped(mmap) => self.find_entries_mapped(mmap, key),
         }
     }
 
@@ -268,46 +430,35 @@ impl PolyglotBook {
         result
     }
 
-    fn find_entries_file(&self, path: &str, key: u64) -> io::Result<Vec<BookEntry>> {
-        let mut file = FsFile::open(path)?;
-        
-        // Binary search in file
+    /// Same binary search as `find_entries_memory`, but reading each entry
+    /// straight out of the mapped bytes instead of a `Vec<BookEntry>` — no
+    /// `seek`/`read_exact` syscall per step, and nothing reopened per probe.
+    fn find_entries_mapped(&self, mmap: &Mmap, key: u64) -> Vec<BookEntry> {
+        let entry_at = |i: usize| -> BookEntry {
+            let bytes: [u8; ENTRY_SIZE] = mmap[i * ENTRY_SIZE..(i + 1) * ENTRY_SIZE]
+                .try_into()
+                .expect("entry_count was derived from the file's exact length");
+            BookEntry::from_bytes(&bytes)
+        };
+
         let mut low = 0usize;
         let mut high = self.entry_count;
-        
         while low < high {
-            let mid = (low + high) / 2;
-            file.seek(SeekFrom::Start((mid * ENTRY_SIZE) as u64))?;
-            
-            let mut bytes = [0u8; 16];
-            file.read_exact(&mut bytes)?;
-            let entry = BookEntry::from_bytes(&bytes);
-            
-            if entry.key < key {
+            let mid = low + (high - low) / 2;
+            if entry_at(mid).key < key {
                 low = mid + 1;
             } else {
                 high = mid;
             }
         }
-        
-        // Collect all entries with this key
+
         let mut result = Vec::new();
         let mut pos = low;
-        
-        while pos < self.entry_count {
-            file.seek(SeekFrom::Start((pos * ENTRY_SIZE) as u64))?;
-            let mut bytes = [0u8; 16];
-            file.read_exact(&mut bytes)?;
-            let entry = BookEntry::from_bytes(&bytes);
-            
-            if entry.key != key {
-                break;
-            }
-            result.push(entry);
+        while pos < self.entry_count && entry_at(pos).key == key {
+            result.push(entry_at(pos));
             pos += 1;
         }
-        
-        Ok(result)
+        result
     }
 
     /// Get the number of entries in the book
@@ -344,4 +495,150 @@ mod tests {
         assert_eq!(to, Square::make_square(Rank::Fourth, File::E));
         assert!(promo.is_none());
     }
+
+    /// e2e4 and d2d4, same encoding scheme as `test_decode_move`.
+    fn starting_position_book(e4_weight: u16, d4_weight: u16) -> PolyglotBook {
+        let board = Board::default();
+        let key = polyglot_hash(&board);
+        PolyglotBook {
+            data: BookData::Memory(vec![
+                BookEntry { key, raw_move: 796, weight: e4_weight, learn: 0 },
+                BookEntry { key, raw_move: 731, weight: d4_weight, learn: 0 },
+            ]),
+            entry_count: 2,
+            desc: "test".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_probe_move_with_rng_zero_temperature_matches_best_move() {
+        let board = Board::default();
+        let book = starting_position_book(100, 900);
+        let mut rng = Rng::new(1);
+        assert_eq!(
+            book.probe_move_with_rng(&board, &mut rng, 0.0),
+            book.probe_best_move(&board),
+        );
+    }
+
+    #[test]
+    fn test_probe_move_with_rng_is_reproducible_with_same_seed() {
+        let board = Board::default();
+        let book = starting_position_book(500, 500);
+
+        let mut rng_a = Rng::new(42);
+        let mut rng_b = Rng::new(42);
+        assert_eq!(
+            book.probe_move_with_rng(&board, &mut rng_a, 1.0),
+            book.probe_move_with_rng(&board, &mut rng_b, 1.0),
+        );
+    }
+
+    #[test]
+    fn test_probe_move_with_rng_low_temperature_favors_heavier_weight() {
+        let board = Board::default();
+        let book = starting_position_book(1, 999);
+        let mut rng = Rng::new(7);
+
+        let mut d4_count = 0;
+        for _ in 0..50 {
+            if book.probe_move_with_rng(&board, &mut rng, 0.2) == book.probe_best_move(&board) {
+                d4_count += 1;
+            }
+        }
+        assert!(d4_count > 45, "expected a low temperature to sample the heavy move almost every time");
+    }
+
+    #[test]
+    fn test_record_result_updates_learn_and_weight_for_the_played_move() {
+        let board = Board::default();
+        let e4 = ChessMove::new(Square::E2, Square::E4, None);
+        let mut book = starting_position_book(100, 100);
+
+        book.record_result(board, &[e4], GameResult::WhiteWin);
+
+        let entry = book.probe(&board).into_iter().find(|e| e.raw_move == 796).unwrap();
+        assert_eq!(entry.weight, 101);
+        assert_eq!(unpack_learn(entry.learn), (1, 2));
+    }
+
+    #[test]
+    fn test_record_result_with_step_scales_the_weight_nudge() {
+        let board = Board::default();
+        let e4 = ChessMove::new(Square::E2, Square::E4, None);
+        let mut book = starting_position_book(100, 100);
+
+        book.record_result_with_step(board, &[e4], GameResult::WhiteWin, 5);
+
+        let entry = book.probe(&board).into_iter().find(|e| e.raw_move == 796).unwrap();
+        assert_eq!(entry.weight, 105);
+    }
+
+    #[test]
+    fn test_record_result_ignores_moves_not_in_the_book() {
+        let board = Board::default();
+        let d4 = ChessMove::new(Square::D2, Square::D4, None);
+        let not_in_book = ChessMove::new(Square::G8, Square::F6, None);
+        let mut book = starting_position_book(100, 100);
+
+        // Shouldn't panic even though `not_in_book`'s position has no entry.
+        book.record_result(board, &[d4, not_in_book], GameResult::Draw);
+    }
+
+    #[test]
+    fn test_save_and_reload_preserves_sort_order_after_record_result() {
+        let board = Board::default();
+        let e4 = ChessMove::new(Square::E2, Square::E4, None);
+        let mut book = starting_position_book(50, 999);
+
+        // Enough wins that e4's nudged weight overtakes d4's.
+        for _ in 0..960 {
+            book.record_result(board, &[e4], GameResult::WhiteWin);
+        }
+
+        let path = std::env::temp_dir().join(format!(
+            "chessgo-test-learn-book-{:?}.bin",
+            std::thread::current().id()
+        ));
+        book.save(&path).unwrap();
+
+        let reloaded = PolyglotBook::load(&path).unwrap();
+        assert_eq!(reloaded.probe_best_move(&board), Some(e4));
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_mapped_book_probing_matches_memory_book_probing() {
+        let board = Board::default();
+        let memory_book = starting_position_book(900, 100);
+
+        let path = std::env::temp_dir().join(format!(
+            "chessgo-test-mapped-book-{:?}.bin",
+            std::thread::current().id()
+        ));
+        memory_book.save(&path).unwrap();
+
+        let file = FsFile::open(&path).unwrap();
+        let mmap = unsafe { Mmap::map(&file).unwrap() };
+        let mapped_book = PolyglotBook {
+            data: BookData::Mapped(mmap),
+            entry_count: 2,
+            desc: path.to_string_lossy().to_string(),
+        };
+
+        let mut memory_entries = memory_book.probe(&board);
+        let mut mapped_entries = mapped_book.probe(&board);
+        memory_entries.sort_by_key(|e| e.raw_move);
+        mapped_entries.sort_by_key(|e| e.raw_move);
+        assert_eq!(memory_entries.len(), 2);
+        for (a, b) in memory_entries.iter().zip(mapped_entries.iter()) {
+            assert_eq!(a.key, b.key);
+            assert_eq!(a.raw_move, b.raw_move);
+            assert_eq!(a.weight, b.weight);
+        }
+        assert_eq!(mapped_book.probe_best_move(&board), memory_book.probe_best_move(&board));
+
+        std::fs::remove_file(&path).ok();
+    }
 }
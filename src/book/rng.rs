@@ -0,0 +1,91 @@
+//! Small, zero-dependency PRNG for reproducible book move selection.
+//!
+//! A splitmix64 generator (same algorithm already used to seed the Zobrist
+//! random table in `crate::book::zobrist`): fast, good enough statistically
+//! for weighted sampling, and needs no `rand` crate dependency.
+
+/// Splitmix64 generator state. Two generators seeded identically produce the
+/// exact same sequence, so callers can fix a seed for reproducible games and
+/// tests, or seed from the clock for variety in normal play.
+#[derive(Debug, Clone, Copy)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Create a generator from an explicit seed. Any `u64` is a valid seed,
+    /// including 0.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Seed from the system clock, for normal (non-reproducible) play.
+    pub fn from_entropy() -> Self {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0x2545F4914F6CDD1D);
+        Self::new(seed)
+    }
+
+    /// Next raw 64-bit value.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..bound`. Returns 0 if `bound` is 0.
+    pub fn next_u64_below(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            return 0;
+        }
+        self.next_u64() % bound
+    }
+
+    /// Uniform value in `[0.0, 1.0)`.
+    pub fn next_f64(&mut self) -> f64 {
+        // Top 53 bits give a value representable exactly as an f64 mantissa.
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_produces_same_sequence() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..8 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_different_seeds_diverge() {
+        let mut a = Rng::new(1);
+        let mut b = Rng::new(2);
+        assert_ne!(a.next_u64(), b.next_u64());
+    }
+
+    #[test]
+    fn test_next_u64_below_bound_is_in_range() {
+        let mut rng = Rng::new(7);
+        for _ in 0..100 {
+            assert!(rng.next_u64_below(10) < 10);
+        }
+    }
+
+    #[test]
+    fn test_next_f64_is_in_unit_range() {
+        let mut rng = Rng::new(99);
+        for _ in 0..100 {
+            let v = rng.next_f64();
+            assert!(v >= 0.0 && v < 1.0);
+        }
+    }
+}
@@ -0,0 +1,328 @@
+//! Build Polyglot (.bin) opening books from played games.
+//!
+//! The inverse of `polyglot::BookEntry` decoding: encode each move into the
+//! 16-bit raw format, accumulate a per-(key, move) occurrence count that
+//! becomes the entry weight, then sort and serialize so the result is
+//! directly loadable by `PolyglotBook::load`. Games can be fed in directly
+//! (`add_game`) or, via `add_pgn`, parsed out of a PGN archive (see
+//! `crate::book::pgn`) with each move's count optionally biased by how its
+//! game turned out.
+
+use super::pgn;
+use super::polyglot::{BookEntry, GameResult};
+use super::zobrist::polyglot_hash;
+use chess::{Board, ChessMove, Color, File, Piece};
+use std::collections::HashMap;
+use std::fs::File as FsFile;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Encode a move into Polyglot's 16-bit raw format (the inverse of
+/// `BookEntry::decode_move`), including the king-captures-rook convention
+/// for castling that `adjust_castling_move` decodes back out. Shared with
+/// `PolyglotBook::record_result`, which needs the same encoding to match a
+/// played move back to its book entry.
+pub(crate) fn encode_move(board: &Board, mv: ChessMove) -> u16 {
+    let from = mv.get_source();
+    let to = mv.get_dest();
+
+    let mut to_file = to.get_file();
+    if board.piece_on(from) == Some(Piece::King) && from.get_file() == File::E {
+        if to_file == File::G {
+            to_file = File::H;
+        } else if to_file == File::C {
+            to_file = File::A;
+        }
+    }
+
+    let promo_bits: u16 = match mv.get_promotion() {
+        Some(Piece::Knight) => 1,
+        Some(Piece::Bishop) => 2,
+        Some(Piece::Rook) => 3,
+        Some(Piece::Queen) => 4,
+        _ => 0,
+    };
+
+    (to_file.to_index() as u16)
+        | ((to.get_rank().to_index() as u16) << 3)
+        | ((from.get_file().to_index() as u16) << 6)
+        | ((from.get_rank().to_index() as u16) << 9)
+        | (promo_bits << 12)
+}
+
+/// How much a single game's moves count toward a move's occurrence count in
+/// `BookWriter::add_game_with_result`, scaled by how the game turned out for
+/// the side that played each move.
+#[derive(Debug, Clone, Copy)]
+pub struct ResultBias {
+    pub win: u32,
+    pub draw: u32,
+    pub loss: u32,
+}
+
+impl ResultBias {
+    /// Every game counts the same regardless of result (`add_game`'s
+    /// behavior, expressed as a bias for callers that want to mix biased
+    /// and unbiased games through the same call).
+    pub const UNIFORM: ResultBias = ResultBias { win: 1, draw: 1, loss: 1 };
+}
+
+impl Default for ResultBias {
+    /// Winning moves count double, drawn and losing moves count once —
+    /// enough to tilt move selection toward proven winners without
+    /// discarding the frequency signal a loss still carries.
+    fn default() -> Self {
+        ResultBias { win: 2, draw: 1, loss: 1 }
+    }
+}
+
+/// Accumulates (position, move) occurrence counts from played games and
+/// serializes them into a Polyglot book.
+pub struct BookWriter {
+    /// (position key, raw move) -> occurrence count, becomes the entry
+    /// weight on `finalize`.
+    counts: HashMap<(u64, u16), u32>,
+    /// Entries with a count below this are dropped on `finalize`.
+    min_count: u32,
+    /// Plies beyond this (0-indexed from each game's start) aren't recorded.
+    max_ply: Option<usize>,
+}
+
+impl BookWriter {
+    pub fn new() -> Self {
+        Self {
+            counts: HashMap::new(),
+            min_count: 1,
+            max_ply: None,
+        }
+    }
+
+    /// Drop entries that occurred fewer than `min_count` times across all
+    /// recorded games.
+    pub fn with_min_count(mut self, min_count: u32) -> Self {
+        self.min_count = min_count.max(1);
+        self
+    }
+
+    /// Stop recording a game's moves past `max_ply` plies from its start.
+    pub fn with_max_ply(mut self, max_ply: usize) -> Self {
+        self.max_ply = Some(max_ply);
+        self
+    }
+
+    /// Record one played move from `board`.
+    pub fn add_position(&mut self, board: &Board, mv: ChessMove) {
+        let key = polyglot_hash(board);
+        let raw_move = encode_move(board, mv);
+        *self.counts.entry((key, raw_move)).or_insert(0) += 1;
+    }
+
+    /// Record every move of a game played from `start`, stopping early if
+    /// `with_max_ply` was set.
+    pub fn add_game(&mut self, start: Board, moves: &[ChessMove]) {
+        let mut board = start;
+        for (ply, &mv) in moves.iter().enumerate() {
+            if self.max_ply.is_some_and(|max| ply >= max) {
+                break;
+            }
+            self.add_position(&board, mv);
+            board = board.make_move_new(mv);
+        }
+    }
+
+    /// Like `add_game`, but each move's contribution to the occurrence
+    /// count is scaled by `bias` according to how `result` went for
+    /// whichever side played it, so a PGN archive's winning lines end up
+    /// with higher book weight than its losing ones even if both were
+    /// played equally often.
+    pub fn add_game_with_result(
+        &mut self,
+        start: Board,
+        moves: &[ChessMove],
+        result: GameResult,
+        bias: ResultBias,
+    ) {
+        let mut board = start;
+        for (ply, &mv) in moves.iter().enumerate() {
+            if self.max_ply.is_some_and(|max| ply >= max) {
+                break;
+            }
+            let increment = match (result, board.side_to_move()) {
+                (GameResult::Draw, _) => bias.draw,
+                (GameResult::WhiteWin, Color::White) | (GameResult::BlackWin, Color::Black) => bias.win,
+                _ => bias.loss,
+            };
+            let key = polyglot_hash(&board);
+            let raw_move = encode_move(&board, mv);
+            *self.counts.entry((key, raw_move)).or_insert(0) += increment;
+            board = board.make_move_new(mv);
+        }
+    }
+
+    /// Parse `pgn` as a stream of PGN games (see `crate::book::pgn`) and
+    /// record each one, biasing counts by result via `add_game_with_result`.
+    /// Games whose result couldn't be read fall back to `add_game`'s
+    /// unbiased counting.
+    pub fn add_pgn(&mut self, pgn_text: &str, bias: ResultBias) {
+        for game in pgn::parse_pgn_games(pgn_text) {
+            match game.result {
+                Some(result) => self.add_game_with_result(Board::default(), &game.moves, result, bias),
+                None => self.add_game(Board::default(), &game.moves),
+            }
+        }
+    }
+
+    /// Build the final, sorted entry list: ascending by key, ties broken by
+    /// descending weight (matching how `PolyglotBook::probe` expects to find
+    /// the heaviest move for a position first after a `binary_search`).
+    pub fn finalize(self) -> Vec<BookEntry> {
+        let mut entries: Vec<BookEntry> = self
+            .counts
+            .into_iter()
+            .filter(|&(_, count)| count >= self.min_count)
+            .map(|((key, raw_move), count)| BookEntry::new(key, raw_move, count.min(u16::MAX as u32) as u16))
+            .collect();
+        entries.sort_by(|a, b| a.key.cmp(&b.key).then(b.weight.cmp(&a.weight)));
+        entries
+    }
+
+    /// Finalize and write the book to `path` as 16-byte big-endian records.
+    pub fn write<P: AsRef<Path>>(self, path: P) -> io::Result<()> {
+        let entries = self.finalize();
+        let mut file = FsFile::create(path)?;
+        for entry in entries {
+            file.write_all(&entry.to_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for BookWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::book::PolyglotBook;
+    use chess::Square;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_encode_move_roundtrips_through_decode() {
+        let board = Board::default();
+        let mv = ChessMove::new(Square::E2, Square::E4, None);
+        let entry = BookEntry::new(polyglot_hash(&board), encode_move(&board, mv), 1);
+        let (from, to, promo) = entry.decode_move();
+        assert_eq!(from, Square::E2);
+        assert_eq!(to, Square::E4);
+        assert!(promo.is_none());
+    }
+
+    #[test]
+    fn test_encode_move_roundtrips_castling() {
+        let board = Board::from_str("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let castle = ChessMove::new(Square::E1, Square::G1, None);
+        let raw = encode_move(&board, castle);
+        let entry = BookEntry::new(polyglot_hash(&board), raw, 1);
+        // Polyglot's own convention: the king "captures" its rook.
+        let (from, to, _) = entry.decode_move();
+        assert_eq!(from, Square::E1);
+        assert_eq!(to, Square::H1);
+        assert_eq!(entry.to_chess_move(&board), Some(castle));
+    }
+
+    #[test]
+    fn test_finalize_sorts_ascending_by_key_then_descending_weight() {
+        let mut writer = BookWriter::new();
+        let board = Board::default();
+        let e4 = ChessMove::new(Square::E2, Square::E4, None);
+        let d4 = ChessMove::new(Square::D2, Square::D4, None);
+
+        writer.add_position(&board, e4);
+        writer.add_position(&board, d4);
+        writer.add_position(&board, d4);
+
+        let entries = writer.finalize();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].key <= entries[1].key);
+        // Same key for both (same start position): heavier weight first.
+        assert_eq!(entries[0].key, entries[1].key);
+        assert!(entries[0].weight >= entries[1].weight);
+        assert_eq!(entries[0].weight, 2);
+        assert_eq!(entries[1].weight, 1);
+    }
+
+    #[test]
+    fn test_add_game_with_result_weighs_winning_side_higher() {
+        let mut writer = BookWriter::new();
+        let board = Board::default();
+        let e4 = ChessMove::new(Square::E2, Square::E4, None);
+        let e5 = ChessMove::new(Square::E7, Square::E5, None);
+
+        writer.add_game_with_result(board, &[e4, e5], GameResult::WhiteWin, ResultBias::default());
+
+        let entries = writer.finalize();
+        let white_entry = entries.iter().find(|e| e.key == polyglot_hash(&board)).unwrap();
+        assert_eq!(white_entry.weight, 2);
+        let after_e4 = board.make_move_new(e4);
+        let black_entry = entries.iter().find(|e| e.key == polyglot_hash(&after_e4)).unwrap();
+        assert_eq!(black_entry.weight, 1);
+    }
+
+    #[test]
+    fn test_add_pgn_parses_games_and_records_moves() {
+        let pgn = "[Event \"Test\"]\n[Result \"1-0\"]\n\n1. e4 e5 2. Nf3 Nc6 1-0\n";
+        let mut writer = BookWriter::new();
+        writer.add_pgn(pgn, ResultBias::default());
+
+        let entries = writer.finalize();
+        assert_eq!(entries.len(), 4);
+        let start_entry = entries.iter().find(|e| e.key == polyglot_hash(&Board::default())).unwrap();
+        assert_eq!(start_entry.weight, 2);
+    }
+
+    #[test]
+    fn test_min_count_prunes_rare_moves() {
+        let mut writer = BookWriter::new().with_min_count(2);
+        let board = Board::default();
+        let e4 = ChessMove::new(Square::E2, Square::E4, None);
+        writer.add_position(&board, e4);
+
+        assert!(writer.finalize().is_empty());
+    }
+
+    #[test]
+    fn test_max_ply_stops_recording_early() {
+        let mut writer = BookWriter::new().with_max_ply(1);
+        let board = Board::default();
+        let e4 = ChessMove::new(Square::E2, Square::E4, None);
+        let after_e4 = board.make_move_new(e4);
+        let e5 = ChessMove::new(Square::E7, Square::E5, None);
+
+        writer.add_game(board, &[e4, e5]);
+
+        let entries = writer.finalize();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, polyglot_hash(&board));
+        let _ = after_e4;
+    }
+
+    #[test]
+    fn test_written_book_round_trips_through_polyglot_book_load() {
+        let mut writer = BookWriter::new();
+        let board = Board::default();
+        let e4 = ChessMove::new(Square::E2, Square::E4, None);
+        writer.add_position(&board, e4);
+
+        let path = std::env::temp_dir().join(format!("chessgo-test-book-{:?}.bin", std::thread::current().id()));
+        writer.write(&path).unwrap();
+
+        let book = PolyglotBook::load(&path).unwrap();
+        assert_eq!(book.probe_best_move(&board), Some(e4));
+
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,184 @@
+//! Zobrist hashing for Polyglot-format opening books.
+//!
+//! A Polyglot key is the XOR of 781 random 64-bit numbers: one per
+//! (piece, color, square) triple (768 keys), one per castling right (4),
+//! one per en-passant file (8), and one for side to move (1). The table is
+//! generated once from a fixed seed via splitmix64 so every build produces
+//! the same keys without shipping a giant literal array.
+
+use chess::{Board, Color, Piece, Rank, Square};
+use std::sync::OnceLock;
+
+const NUM_RANDOMS: usize = 781;
+const PIECE_OFFSET: usize = 0;
+const CASTLE_OFFSET: usize = 768;
+const ENPASSANT_OFFSET: usize = 772;
+const TURN_OFFSET: usize = 780;
+
+/// Deterministically seeded random table, built once on first use.
+fn randoms() -> &'static [u64; NUM_RANDOMS] {
+    static TABLE: OnceLock<[u64; NUM_RANDOMS]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut seed: u64 = 0x9E3779B97F4A7C15;
+        let mut table = [0u64; NUM_RANDOMS];
+        for slot in table.iter_mut() {
+            // splitmix64
+            seed = seed.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            *slot = z ^ (z >> 31);
+        }
+        table
+    })
+}
+
+/// Polyglot piece index: BP=0, WP=1, BN=2, WN=3, ..., BK=10, WK=11.
+#[inline]
+fn polyglot_piece_index(piece: Piece, color: Color) -> usize {
+    let base = match piece {
+        Piece::Pawn => 0,
+        Piece::Knight => 2,
+        Piece::Bishop => 4,
+        Piece::Rook => 6,
+        Piece::Queen => 8,
+        Piece::King => 10,
+    };
+    base + if color == Color::White { 1 } else { 0 }
+}
+
+/// True if the side to move has a pawn on a file adjacent to `ep`'s, sitting
+/// on the rank it would capture from — i.e. an en-passant capture is
+/// actually legal-looking in this position (ignores pins, same as PolyGlot).
+/// `ep` is the FEN-style en-passant square (the square a capturing pawn
+/// would land on), one rank behind/ahead of the vulnerable pawn itself.
+fn ep_capture_available(board: &Board, ep: Square) -> bool {
+    let side = board.side_to_move();
+    let capture_rank = match side {
+        Color::White => ep.get_rank().to_index().wrapping_sub(1),
+        Color::Black => ep.get_rank().to_index() + 1,
+    };
+    if capture_rank > 7 {
+        return false;
+    }
+    let capture_rank = Rank::from_index(capture_rank);
+    let ep_file = ep.get_file().to_index();
+    let pawns = board.pieces(Piece::Pawn) & board.color_combined(side);
+
+    [ep_file.checked_sub(1), (ep_file + 1 <= 7).then(|| ep_file + 1)]
+        .into_iter()
+        .flatten()
+        .any(|file| {
+            let sq = Square::make_square(capture_rank, chess::File::from_index(file));
+            (pawns & chess::BitBoard::from_square(sq)) != chess::EMPTY
+        })
+}
+
+/// Compute the Polyglot Zobrist hash for a position.
+pub fn polyglot_hash(board: &Board) -> u64 {
+    let r = randoms();
+    let mut key = 0u64;
+
+    for sq in chess::ALL_SQUARES {
+        if let Some(piece) = board.piece_on(sq) {
+            let color = board.color_on(sq).expect("occupied square has a color");
+            let p = polyglot_piece_index(piece, color);
+            key ^= r[PIECE_OFFSET + 64 * p + sq.to_index()];
+        }
+    }
+
+    let white_rights = board.castle_rights(Color::White);
+    if white_rights.has_kingside() {
+        key ^= r[CASTLE_OFFSET];
+    }
+    if white_rights.has_queenside() {
+        key ^= r[CASTLE_OFFSET + 1];
+    }
+    let black_rights = board.castle_rights(Color::Black);
+    if black_rights.has_kingside() {
+        key ^= r[CASTLE_OFFSET + 2];
+    }
+    if black_rights.has_queenside() {
+        key ^= r[CASTLE_OFFSET + 3];
+    }
+
+    // PolyGlot only includes the en-passant file key when a capture is
+    // actually available: the side to move must have a pawn adjacent to
+    // the just-moved pawn, on the rank it would capture from. Otherwise the
+    // ep file must be ignored entirely, or keys diverge from every real
+    // PolyGlot book (which generates ep squares the same conservative way).
+    if let Some(ep) = board.en_passant() {
+        if ep_capture_available(board, ep) {
+            key ^= r[ENPASSANT_OFFSET + ep.get_file().to_index()];
+        }
+    }
+
+    if board.side_to_move() == Color::Black {
+        key ^= r[TURN_OFFSET];
+    }
+
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_hash_is_deterministic() {
+        let board = Board::default();
+        assert_eq!(polyglot_hash(&board), polyglot_hash(&board));
+    }
+
+    #[test]
+    fn test_ep_key_included_when_capture_is_available() {
+        // White pawn on e5, Black just played d7-d5: d6 is the EP square,
+        // and White's e5 pawn can actually capture there.
+        let with_ep = Board::from_str(
+            "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+        ).unwrap();
+        let without_ep_flag = Board::from_str(
+            "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq - 0 3",
+        ).unwrap();
+
+        let ep_key = randoms()[ENPASSANT_OFFSET + chess::File::D.to_index()];
+        assert_eq!(polyglot_hash(&with_ep), polyglot_hash(&without_ep_flag) ^ ep_key);
+    }
+
+    #[test]
+    fn test_ep_key_ignored_when_no_pawn_can_capture() {
+        // Same EP flag (d6), but no White pawn anywhere near it: PolyGlot
+        // must ignore the EP file entirely rather than include its key.
+        let with_ep = Board::from_str(
+            "rnbqkbnr/ppp1pppp/8/3p4/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+        ).unwrap();
+        let without_ep_flag = Board::from_str(
+            "rnbqkbnr/ppp1pppp/8/3p4/8/8/PPPP1PPP/RNBQKBNR w KQkq - 0 3",
+        ).unwrap();
+
+        assert_eq!(polyglot_hash(&with_ep), polyglot_hash(&without_ep_flag));
+    }
+
+    #[test]
+    fn test_castling_rights_use_four_independent_keys() {
+        let all_rights = Board::default();
+        let no_rights = Board::from_str(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1",
+        ).unwrap();
+
+        let r = randoms();
+        let all_castle_keys = r[CASTLE_OFFSET] ^ r[CASTLE_OFFSET + 1] ^ r[CASTLE_OFFSET + 2] ^ r[CASTLE_OFFSET + 3];
+        assert_eq!(polyglot_hash(&all_rights), polyglot_hash(&no_rights) ^ all_castle_keys);
+    }
+
+    #[test]
+    fn test_side_to_move_key_applies_only_to_black() {
+        let white_to_move = Board::default();
+        let black_to_move = Board::from_str(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1",
+        ).unwrap();
+
+        assert_eq!(polyglot_hash(&black_to_move), polyglot_hash(&white_to_move) ^ randoms()[TURN_OFFSET]);
+    }
+}
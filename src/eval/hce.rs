@@ -7,146 +7,476 @@
 //! - Pawn structure (doubled, isolated, passed)
 //! - King safety (midgame) and centralization (endgame)
 //! - Endgame-specific bonuses
+//! - Endgame scale factor (opposite-colored bishops, wrong rook pawn,
+//!   insufficient mating material)
 
 use crate::types::{Board, Score};
-use chess::{Color, Piece, Square, BitBoard, Rank, File, EMPTY};
+use chess::{
+    Color, Piece, Square, BitBoard, Rank, File, EMPTY,
+    get_knight_moves, get_bishop_moves, get_rook_moves, get_king_moves,
+};
+use std::cell::RefCell;
+use std::sync::OnceLock;
 
 // ============================================================================
-// PIECE VALUES (centipawns)
+// TUNABLE EVALUATION PARAMETERS
 // ============================================================================
+//
+// Every weight the evaluator reads lives here rather than in free-standing
+// `const`s, so `tuning::tune` can nudge them via coordinate descent against
+// a labeled set of positions. `EvalParams::default()` reproduces the values
+// this file shipped with before the refactor.
 
-const PAWN_MG: i32 = 100;
-const KNIGHT_MG: i32 = 320;
-const BISHOP_MG: i32 = 330;
-const ROOK_MG: i32 = 500;
-const QUEEN_MG: i32 = 900;
+/// All tunable weights used by [`evaluate_with`]: material, piece-square
+/// tables, and every structural bonus/penalty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvalParams {
+    pub pawn_mg: i32,
+    pub knight_mg: i32,
+    pub bishop_mg: i32,
+    pub rook_mg: i32,
+    pub queen_mg: i32,
 
-const PAWN_EG: i32 = 120;
-const KNIGHT_EG: i32 = 300;
-const BISHOP_EG: i32 = 320;
-const ROOK_EG: i32 = 550;
-const QUEEN_EG: i32 = 950;
+    pub pawn_eg: i32,
+    pub knight_eg: i32,
+    pub bishop_eg: i32,
+    pub rook_eg: i32,
+    pub queen_eg: i32,
 
-// ============================================================================
-// PIECE-SQUARE TABLES (from white's perspective, a1=0)
-// ============================================================================
+    pub pawn_pst_mg: [i32; 64],
+    pub pawn_pst_eg: [i32; 64],
+    pub knight_pst_mg: [i32; 64],
+    pub bishop_pst_mg: [i32; 64],
+    pub rook_pst_mg: [i32; 64],
+    pub queen_pst_mg: [i32; 64],
+    pub king_pst_mg: [i32; 64],
+    pub king_pst_eg: [i32; 64],
+
+    pub bishop_pair_bonus: i32,
+    // Doubled/isolated pawns are weaker when there's no enemy pawn blocking
+    // the file (unopposed/open): nothing stops them from ever becoming a
+    // target.
+    pub doubled_pawn_penalty_opposed: i32,
+    pub doubled_pawn_penalty_unopposed: i32,
+    pub isolated_pawn_penalty_open_file: i32,
+    pub isolated_pawn_penalty_closed_file: i32,
+    // A pawn that can't safely advance because its stop square is covered
+    // by an enemy pawn and no friendly pawn on an adjacent file can support
+    // it.
+    pub backward_pawn_penalty: (i32, i32),
+    // Connected pawns (mutually defending or forming a phalanx), by rank
+    // (2-7). Doubled for a phalanx, halved when opposed by an enemy pawn on
+    // the file.
+    pub connected_pawn_bonus: [(i32, i32); 8],
+    pub passed_pawn_bonus: [i32; 8], // by rank (2-7)
+    pub rook_on_open_file: i32,
+    pub rook_on_semi_open: i32,
+    pub rook_on_7th: i32,
+
+    // (mg, eg) bonus indexed by count of mobility-area squares attacked, à
+    // la Stockfish's MobilityBonus: trapped pieces (low count) are
+    // penalized, active ones rewarded, flattening out once a piece has
+    // plenty of squares.
+    pub knight_mobility: [(i32, i32); 9],
+    pub bishop_mobility: [(i32, i32); 14],
+    pub rook_mobility: [(i32, i32); 15],
+    pub queen_mobility: [(i32, i32); 28],
+
+    // Per-piece weight applied to the count of enemy king-ring squares a
+    // piece attacks, à la Stockfish's KingAttackWeights.
+    pub knight_king_attack_weight: i32,
+    pub bishop_king_attack_weight: i32,
+    pub rook_king_attack_weight: i32,
+    pub queen_king_attack_weight: i32,
+
+    // Flat penalty per undefended check square a piece type could deliver
+    // from.
+    pub knight_safe_check_penalty: i32,
+    pub bishop_safe_check_penalty: i32,
+    pub rook_safe_check_penalty: i32,
+    pub queen_safe_check_penalty: i32,
+}
+
+impl Default for EvalParams {
+    #[rustfmt::skip]
+    fn default() -> Self {
+        Self {
+            pawn_mg: 100, knight_mg: 320, bishop_mg: 330, rook_mg: 500, queen_mg: 900,
+            pawn_eg: 120, knight_eg: 300, bishop_eg: 320, rook_eg: 550, queen_eg: 950,
+
+            pawn_pst_mg: [
+                 0,  0,  0,  0,  0,  0,  0,  0,
+                50, 50, 50, 50, 50, 50, 50, 50,
+                10, 10, 20, 30, 30, 20, 10, 10,
+                 5,  5, 10, 25, 25, 10,  5,  5,
+                 0,  0,  0, 20, 20,  0,  0,  0,
+                 5, -5,-10,  0,  0,-10, -5,  5,
+                 5, 10, 10,-20,-20, 10, 10,  5,
+                 0,  0,  0,  0,  0,  0,  0,  0,
+            ],
+            pawn_pst_eg: [
+                 0,  0,  0,  0,  0,  0,  0,  0,
+                80, 80, 80, 80, 80, 80, 80, 80,
+                50, 50, 50, 50, 50, 50, 50, 50,
+                30, 30, 30, 30, 30, 30, 30, 30,
+                20, 20, 20, 20, 20, 20, 20, 20,
+                10, 10, 10, 10, 10, 10, 10, 10,
+                 5,  5,  5,  5,  5,  5,  5,  5,
+                 0,  0,  0,  0,  0,  0,  0,  0,
+            ],
+            knight_pst_mg: [
+               -50,-40,-30,-30,-30,-30,-40,-50,
+               -40,-20,  0,  0,  0,  0,-20,-40,
+               -30,  0, 10, 15, 15, 10,  0,-30,
+               -30,  5, 15, 20, 20, 15,  5,-30,
+               -30,  0, 15, 20, 20, 15,  0,-30,
+               -30,  5, 10, 15, 15, 10,  5,-30,
+               -40,-20,  0,  5,  5,  0,-20,-40,
+               -50,-40,-30,-30,-30,-30,-40,-50,
+            ],
+            bishop_pst_mg: [
+               -20,-10,-10,-10,-10,-10,-10,-20,
+               -10,  0,  0,  0,  0,  0,  0,-10,
+               -10,  0,  5, 10, 10,  5,  0,-10,
+               -10,  5,  5, 10, 10,  5,  5,-10,
+               -10,  0, 10, 10, 10, 10,  0,-10,
+               -10, 10, 10, 10, 10, 10, 10,-10,
+               -10,  5,  0,  0,  0,  0,  5,-10,
+               -20,-10,-10,-10,-10,-10,-10,-20,
+            ],
+            rook_pst_mg: [
+                 0,  0,  0,  0,  0,  0,  0,  0,
+                 5, 10, 10, 10, 10, 10, 10,  5,
+                -5,  0,  0,  0,  0,  0,  0, -5,
+                -5,  0,  0,  0,  0,  0,  0, -5,
+                -5,  0,  0,  0,  0,  0,  0, -5,
+                -5,  0,  0,  0,  0,  0,  0, -5,
+                -5,  0,  0,  0,  0,  0,  0, -5,
+                 0,  0,  0,  5,  5,  0,  0,  0,
+            ],
+            queen_pst_mg: [
+               -20,-10,-10, -5, -5,-10,-10,-20,
+               -10,  0,  0,  0,  0,  0,  0,-10,
+               -10,  0,  5,  5,  5,  5,  0,-10,
+                -5,  0,  5,  5,  5,  5,  0, -5,
+                 0,  0,  5,  5,  5,  5,  0, -5,
+               -10,  5,  5,  5,  5,  5,  0,-10,
+               -10,  0,  5,  0,  0,  0,  0,-10,
+               -20,-10,-10, -5, -5,-10,-10,-20,
+            ],
+            king_pst_mg: [
+               -30,-40,-40,-50,-50,-40,-40,-30,
+               -30,-40,-40,-50,-50,-40,-40,-30,
+               -30,-40,-40,-50,-50,-40,-40,-30,
+               -30,-40,-40,-50,-50,-40,-40,-30,
+               -20,-30,-30,-40,-40,-30,-30,-20,
+               -10,-20,-20,-20,-20,-20,-20,-10,
+                20, 20,  0,  0,  0,  0, 20, 20,
+                20, 30, 10,  0,  0, 10, 30, 20,
+            ],
+            king_pst_eg: [
+               -50,-40,-30,-20,-20,-30,-40,-50,
+               -30,-20,-10,  0,  0,-10,-20,-30,
+               -30,-10, 20, 30, 30, 20,-10,-30,
+               -30,-10, 30, 40, 40, 30,-10,-30,
+               -30,-10, 30, 40, 40, 30,-10,-30,
+               -30,-10, 20, 30, 30, 20,-10,-30,
+               -30,-30,  0,  0,  0,  0,-30,-30,
+               -50,-30,-30,-30,-30,-30,-30,-50,
+            ],
+
+            bishop_pair_bonus: 30,
+            doubled_pawn_penalty_opposed: -8,
+            doubled_pawn_penalty_unopposed: -14,
+            isolated_pawn_penalty_open_file: -27,
+            isolated_pawn_penalty_closed_file: -15,
+            backward_pawn_penalty: (-9, -24),
+            connected_pawn_bonus: [
+                (0, 0), (5, 5), (7, 7), (11, 11), (23, 17), (43, 31), (78, 56), (0, 0),
+            ],
+            passed_pawn_bonus: [0, 10, 20, 40, 60, 90, 130, 0],
+            rook_on_open_file: 20,
+            rook_on_semi_open: 10,
+            rook_on_7th: 30,
+
+            knight_mobility: [
+                (-40, -50), (-22, -30), (-8, -12), (2, 0), (10, 8), (18, 14), (24, 18), (28, 20), (30, 22),
+            ],
+            bishop_mobility: [
+                (-40, -50), (-22, -30), (-8, -15), (2, -2), (10, 8), (16, 14), (22, 20), (26, 24),
+                (30, 28), (32, 30), (34, 32), (35, 33), (36, 34), (37, 35),
+            ],
+            rook_mobility: [
+                (-40, -60), (-22, -35), (-8, -15), (-2, 0), (0, 10), (4, 20), (8, 28), (12, 36),
+                (16, 44), (18, 50), (20, 56), (22, 60), (24, 64), (26, 67), (28, 70),
+            ],
+            queen_mobility: [
+                (-30, -40), (-18, -28), (-8, -16), (-2, -6), (2, 4), (6, 12), (10, 18), (13, 22),
+                (16, 26), (18, 29), (20, 32), (22, 34), (24, 36), (25, 38), (26, 40), (27, 42),
+                (28, 44), (29, 46), (30, 47), (31, 48), (32, 49), (33, 50), (34, 51), (35, 52),
+                (36, 53), (37, 54), (38, 55), (39, 56),
+            ],
+
+            knight_king_attack_weight: 81,
+            bishop_king_attack_weight: 52,
+            rook_king_attack_weight: 44,
+            queen_king_attack_weight: 10,
+
+            knight_safe_check_penalty: 600,
+            bishop_safe_check_penalty: 600,
+            rook_safe_check_penalty: 1000,
+            queen_safe_check_penalty: 780,
+        }
+    }
+}
+
+impl EvalParams {
+    /// Mutable references to every scalar weight, flattened out of the
+    /// arrays and tuple pairs above, for generic coordinate-descent tuning.
+    pub(crate) fn scalars_mut(&mut self) -> Vec<&mut i32> {
+        let mut v = Vec::new();
+        v.push(&mut self.pawn_mg);
+        v.push(&mut self.knight_mg);
+        v.push(&mut self.bishop_mg);
+        v.push(&mut self.rook_mg);
+        v.push(&mut self.queen_mg);
+        v.push(&mut self.pawn_eg);
+        v.push(&mut self.knight_eg);
+        v.push(&mut self.bishop_eg);
+        v.push(&mut self.rook_eg);
+        v.push(&mut self.queen_eg);
 
-// Pawn PST (encourage center control and advancement)
-#[rustfmt::skip]
-const PAWN_PST_MG: [i32; 64] = [
-     0,  0,  0,  0,  0,  0,  0,  0,
-    50, 50, 50, 50, 50, 50, 50, 50,
-    10, 10, 20, 30, 30, 20, 10, 10,
-     5,  5, 10, 25, 25, 10,  5,  5,
-     0,  0,  0, 20, 20,  0,  0,  0,
-     5, -5,-10,  0,  0,-10, -5,  5,
-     5, 10, 10,-20,-20, 10, 10,  5,
-     0,  0,  0,  0,  0,  0,  0,  0,
-];
-
-#[rustfmt::skip]
-const PAWN_PST_EG: [i32; 64] = [
-     0,  0,  0,  0,  0,  0,  0,  0,
-    80, 80, 80, 80, 80, 80, 80, 80,
-    50, 50, 50, 50, 50, 50, 50, 50,
-    30, 30, 30, 30, 30, 30, 30, 30,
-    20, 20, 20, 20, 20, 20, 20, 20,
-    10, 10, 10, 10, 10, 10, 10, 10,
-     5,  5,  5,  5,  5,  5,  5,  5,
-     0,  0,  0,  0,  0,  0,  0,  0,
-];
-
-// Knight PST (encourage centralization)
-#[rustfmt::skip]
-const KNIGHT_PST_MG: [i32; 64] = [
-   -50,-40,-30,-30,-30,-30,-40,-50,
-   -40,-20,  0,  0,  0,  0,-20,-40,
-   -30,  0, 10, 15, 15, 10,  0,-30,
-   -30,  5, 15, 20, 20, 15,  5,-30,
-   -30,  0, 15, 20, 20, 15,  0,-30,
-   -30,  5, 10, 15, 15, 10,  5,-30,
-   -40,-20,  0,  5,  5,  0,-20,-40,
-   -50,-40,-30,-30,-30,-30,-40,-50,
-];
-
-// Bishop PST
-#[rustfmt::skip]
-const BISHOP_PST_MG: [i32; 64] = [
-   -20,-10,-10,-10,-10,-10,-10,-20,
-   -10,  0,  0,  0,  0,  0,  0,-10,
-   -10,  0,  5, 10, 10,  5,  0,-10,
-   -10,  5,  5, 10, 10,  5,  5,-10,
-   -10,  0, 10, 10, 10, 10,  0,-10,
-   -10, 10, 10, 10, 10, 10, 10,-10,
-   -10,  5,  0,  0,  0,  0,  5,-10,
-   -20,-10,-10,-10,-10,-10,-10,-20,
-];
-
-// Rook PST (7th rank bonus, open files)
-#[rustfmt::skip]
-const ROOK_PST_MG: [i32; 64] = [
-     0,  0,  0,  0,  0,  0,  0,  0,
-     5, 10, 10, 10, 10, 10, 10,  5,
-    -5,  0,  0,  0,  0,  0,  0, -5,
-    -5,  0,  0,  0,  0,  0,  0, -5,
-    -5,  0,  0,  0,  0,  0,  0, -5,
-    -5,  0,  0,  0,  0,  0,  0, -5,
-    -5,  0,  0,  0,  0,  0,  0, -5,
-     0,  0,  0,  5,  5,  0,  0,  0,
-];
-
-// Queen PST
-#[rustfmt::skip]
-const QUEEN_PST_MG: [i32; 64] = [
-   -20,-10,-10, -5, -5,-10,-10,-20,
-   -10,  0,  0,  0,  0,  0,  0,-10,
-   -10,  0,  5,  5,  5,  5,  0,-10,
-    -5,  0,  5,  5,  5,  5,  0, -5,
-     0,  0,  5,  5,  5,  5,  0, -5,
-   -10,  5,  5,  5,  5,  5,  0,-10,
-   -10,  0,  5,  0,  0,  0,  0,-10,
-   -20,-10,-10, -5, -5,-10,-10,-20,
-];
-
-// King PST - midgame (encourage castling, hide)
-#[rustfmt::skip]
-const KING_PST_MG: [i32; 64] = [
-   -30,-40,-40,-50,-50,-40,-40,-30,
-   -30,-40,-40,-50,-50,-40,-40,-30,
-   -30,-40,-40,-50,-50,-40,-40,-30,
-   -30,-40,-40,-50,-50,-40,-40,-30,
-   -20,-30,-30,-40,-40,-30,-30,-20,
-   -10,-20,-20,-20,-20,-20,-20,-10,
-    20, 20,  0,  0,  0,  0, 20, 20,
-    20, 30, 10,  0,  0, 10, 30, 20,
-];
-
-// King PST - endgame (encourage centralization)
-#[rustfmt::skip]
-const KING_PST_EG: [i32; 64] = [
-   -50,-40,-30,-20,-20,-30,-40,-50,
-   -30,-20,-10,  0,  0,-10,-20,-30,
-   -30,-10, 20, 30, 30, 20,-10,-30,
-   -30,-10, 30, 40, 40, 30,-10,-30,
-   -30,-10, 30, 40, 40, 30,-10,-30,
-   -30,-10, 20, 30, 30, 20,-10,-30,
-   -30,-30,  0,  0,  0,  0,-30,-30,
-   -50,-30,-30,-30,-30,-30,-30,-50,
-];
+        for table in [
+            &mut self.pawn_pst_mg, &mut self.pawn_pst_eg,
+            &mut self.knight_pst_mg, &mut self.bishop_pst_mg,
+            &mut self.rook_pst_mg, &mut self.queen_pst_mg,
+            &mut self.king_pst_mg, &mut self.king_pst_eg,
+        ] {
+            v.extend(table.iter_mut());
+        }
+
+        v.push(&mut self.bishop_pair_bonus);
+        v.push(&mut self.doubled_pawn_penalty_opposed);
+        v.push(&mut self.doubled_pawn_penalty_unopposed);
+        v.push(&mut self.isolated_pawn_penalty_open_file);
+        v.push(&mut self.isolated_pawn_penalty_closed_file);
+        v.push(&mut self.backward_pawn_penalty.0);
+        v.push(&mut self.backward_pawn_penalty.1);
+        for pair in self.connected_pawn_bonus.iter_mut() {
+            v.push(&mut pair.0);
+            v.push(&mut pair.1);
+        }
+        v.extend(self.passed_pawn_bonus.iter_mut());
+        v.push(&mut self.rook_on_open_file);
+        v.push(&mut self.rook_on_semi_open);
+        v.push(&mut self.rook_on_7th);
+
+        for pair in self.knight_mobility.iter_mut() {
+            v.push(&mut pair.0);
+            v.push(&mut pair.1);
+        }
+        for pair in self.bishop_mobility.iter_mut() {
+            v.push(&mut pair.0);
+            v.push(&mut pair.1);
+        }
+        for pair in self.rook_mobility.iter_mut() {
+            v.push(&mut pair.0);
+            v.push(&mut pair.1);
+        }
+        for pair in self.queen_mobility.iter_mut() {
+            v.push(&mut pair.0);
+            v.push(&mut pair.1);
+        }
+
+        v.push(&mut self.knight_king_attack_weight);
+        v.push(&mut self.bishop_king_attack_weight);
+        v.push(&mut self.rook_king_attack_weight);
+        v.push(&mut self.queen_king_attack_weight);
+        v.push(&mut self.knight_safe_check_penalty);
+        v.push(&mut self.bishop_safe_check_penalty);
+        v.push(&mut self.rook_safe_check_penalty);
+        v.push(&mut self.queen_safe_check_penalty);
+
+        v
+    }
+}
+
+/// Lazily-built default parameter set, shared by every `evaluate` call that
+/// doesn't supply its own (i.e. every call outside of tuning).
+fn default_params() -> &'static EvalParams {
+    static PARAMS: OnceLock<EvalParams> = OnceLock::new();
+    PARAMS.get_or_init(EvalParams::default)
+}
 
 // ============================================================================
-// BONUSES AND PENALTIES
+// PAWN HASH TABLE
 // ============================================================================
+//
+// Doubled/isolated/passed-pawn evaluation only depends on the two pawn
+// bitboards, which change far less often than the rest of the position
+// across a search tree. Cache the resulting (mg, eg) delta (plus the passed
+// pawn bitboards, for reuse by future king-safety / passed-pawn-race code)
+// keyed by a pawn-only Zobrist key, independent of `Board::hash()`.
+
+const PAWN_HASH_BITS: usize = 14;
+const PAWN_HASH_SIZE: usize = 1 << PAWN_HASH_BITS;
+const PAWN_HASH_MASK: usize = PAWN_HASH_SIZE - 1;
 
-const BISHOP_PAIR_BONUS: i32 = 30;
-const DOUBLED_PAWN_PENALTY: i32 = -10;
-const ISOLATED_PAWN_PENALTY: i32 = -20;
-const PASSED_PAWN_BONUS: [i32; 8] = [0, 10, 20, 40, 60, 90, 130, 0]; // by rank (2-7)
-const ROOK_ON_OPEN_FILE: i32 = 20;
-const ROOK_ON_SEMI_OPEN: i32 = 10;
-const ROOK_ON_7TH: i32 = 30;
-// Reserved for future mobility evaluation
-// const MOBILITY_BONUS: i32 = 3; // per legal move
+#[derive(Clone, Copy)]
+struct PawnEntry {
+    /// Full pawn-only Zobrist key, for collision verification.
+    key: u64,
+    mg: i32,
+    eg: i32,
+    /// Passed-pawn bitboard per color (White, Black).
+    passed: [BitBoard; 2],
+}
+
+impl Default for PawnEntry {
+    fn default() -> Self {
+        Self { key: 0, mg: 0, eg: 0, passed: [EMPTY, EMPTY] }
+    }
+}
+
+thread_local! {
+    static PAWN_HASH: RefCell<Vec<PawnEntry>> = RefCell::new(vec![PawnEntry::default(); PAWN_HASH_SIZE]);
+}
+
+/// Deterministically seeded (piece color, square) keys for pawns only,
+/// built once on first use. Mirrors `book::zobrist`'s splitmix64 seeding
+/// but with its own table, since this key must stay independent of the
+/// full-position Polyglot hash.
+fn pawn_randoms() -> &'static [u64; 128] {
+    static TABLE: OnceLock<[u64; 128]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut seed: u64 = 0xD1B54A32D192ED03;
+        let mut table = [0u64; 128];
+        for slot in table.iter_mut() {
+            seed = seed.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            *slot = z ^ (z >> 31);
+        }
+        table
+    })
+}
+
+/// Pawn-only Zobrist key: XOR of a (color, square) key for every pawn on
+/// the board, recomputed from the two pawn bitboards.
+fn pawn_key(board: &Board) -> u64 {
+    let r = pawn_randoms();
+    let mut key = 0u64;
+    for &color in &[Color::White, Color::Black] {
+        let offset = if color == Color::White { 0 } else { 64 };
+        for sq in board.pieces(Piece::Pawn) & board.color_combined(color) {
+            key ^= r[offset + sq.to_index()];
+        }
+    }
+    key
+}
+
+/// Compute the doubled/isolated/backward/connected/passed-pawn (mg, eg)
+/// delta and passed-pawn bitboards for both colors.
+fn compute_pawn_structure(board: &Board, key: u64, params: &EvalParams) -> PawnEntry {
+    let mut mg = 0;
+    let mut eg = 0;
+    let mut passed = [EMPTY, EMPTY];
+
+    for &color in &[Color::White, Color::Black] {
+        let sign = if color == Color::White { 1 } else { -1 };
+        for sq in board.pieces(Piece::Pawn) & board.color_combined(color) {
+            let file = sq.get_file();
+            let opposed = is_opposed_pawn(board, sq, color);
+
+            if pawns_on_file(board, color, file) > 1 {
+                let penalty = if opposed { params.doubled_pawn_penalty_opposed } else { params.doubled_pawn_penalty_unopposed };
+                mg += sign * penalty;
+                eg += sign * penalty;
+            }
+
+            if is_isolated_pawn(board, sq, color) {
+                let penalty = if is_open_file(board, file) {
+                    params.isolated_pawn_penalty_open_file
+                } else {
+                    params.isolated_pawn_penalty_closed_file
+                };
+                mg += sign * penalty;
+                eg += sign * penalty;
+            }
+
+            if is_backward_pawn(board, sq, color) {
+                mg += sign * params.backward_pawn_penalty.0;
+                eg += sign * params.backward_pawn_penalty.1;
+            }
+
+            let (supported, phalanx) = pawn_connections(board, sq, color);
+            if supported || phalanx {
+                let rank_idx = if color == Color::White {
+                    sq.get_rank() as usize
+                } else {
+                    7 - sq.get_rank() as usize
+                };
+                let (mut conn_mg, mut conn_eg) = params.connected_pawn_bonus[rank_idx.min(7)];
+                if phalanx {
+                    conn_mg *= 2;
+                    conn_eg *= 2;
+                }
+                if opposed {
+                    conn_mg /= 2;
+                    conn_eg /= 2;
+                }
+                mg += sign * conn_mg;
+                eg += sign * conn_eg;
+            }
+
+            if is_passed_pawn(board, sq, color) {
+                let rank = sq.get_rank();
+                let rank_idx = if color == Color::White {
+                    rank as usize
+                } else {
+                    7 - rank as usize
+                };
+                let bonus = params.passed_pawn_bonus[rank_idx.min(7)];
+                mg += sign * bonus / 2;
+                eg += sign * bonus;
+                let color_idx = if color == Color::White { 0 } else { 1 };
+                passed[color_idx] |= BitBoard::from_square(sq);
+            }
+        }
+    }
+
+    PawnEntry { key, mg, eg, passed }
+}
+
+/// Probe the pawn hash table, computing and storing on a miss.
+fn probe_pawn_structure(board: &Board, params: &EvalParams) -> PawnEntry {
+    let key = pawn_key(board);
+    let idx = (key as usize) & PAWN_HASH_MASK;
+
+    PAWN_HASH.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let entry = cache[idx];
+        if entry.key == key {
+            return entry;
+        }
+        let entry = compute_pawn_structure(board, key, params);
+        cache[idx] = entry;
+        entry
+    })
+}
+
+/// Flush the thread-local pawn hash. The cache is only valid for the
+/// `EvalParams` it was populated under; the default params never change at
+/// runtime, but `tuning::tune` mutates a working copy repeatedly and must
+/// call this after every change to avoid serving stale entries.
+pub(crate) fn clear_pawn_hash() {
+    PAWN_HASH.with(|cache| {
+        for entry in cache.borrow_mut().iter_mut() {
+            *entry = PawnEntry::default();
+        }
+    });
+}
 
 // ============================================================================
 // GAME PHASE
@@ -180,6 +510,147 @@ fn taper(mg: i32, eg: i32, phase: i32) -> i32 {
     ((mg * (256 - phase)) + (eg * phase)) / 256
 }
 
+// ============================================================================
+// ENDGAME SCALE FACTOR
+// ============================================================================
+//
+// Tapered interpolation alone overvalues a number of technically drawn
+// endgames, since it only ever looks at material and PST terms. Before
+// tapering, shrink `eg_score` by `scale / SCALE_NORMAL` to pull these
+// positions back toward a draw.
+
+/// No reduction: `eg_score` is used at full weight.
+const SCALE_NORMAL: i32 = 64;
+/// Opposite-colored bishops with nothing else on the board are notoriously
+/// drawish even a pawn or two down — still some winning chances, so not a
+/// hard zero.
+const SCALE_OCB_NO_OTHER_PIECES: i32 = 20;
+/// A dead draw: wrong-colored-bishop rook pawn fortresses and insufficient
+/// mating material.
+const SCALE_DRAW: i32 = 0;
+
+/// A square's color never changes, so this also identifies which diagonal
+/// color a bishop standing on it controls.
+#[inline]
+fn is_dark_square(sq: Square) -> bool {
+    (sq.get_rank().to_index() + sq.get_file().to_index()) % 2 == 0
+}
+
+/// Detects the "wrong bishop + rook pawn" fortress: `color` has a lone
+/// bishop plus pawns confined to the A-file or H-file and nothing else,
+/// the bishop doesn't control that file's queening square, and the
+/// defending king already sits in (or right next to) that corner. Chebyshev
+/// distance <= 1 only catches the king-already-there case; a real king race
+/// needs search, not a static heuristic like this one.
+fn wrong_bishop_rook_pawn_scale(board: &Board) -> Option<i32> {
+    for &color in &[Color::White, Color::Black] {
+        let pawns = board.pieces(Piece::Pawn) & board.color_combined(color);
+        if pawns == EMPTY {
+            continue;
+        }
+        let bishops = board.pieces(Piece::Bishop) & board.color_combined(color);
+        if bishops.popcnt() != 1 {
+            continue;
+        }
+        let other_material = (board.pieces(Piece::Knight) | board.pieces(Piece::Rook) | board.pieces(Piece::Queen))
+            & board.color_combined(color);
+        if other_material != EMPTY {
+            continue;
+        }
+
+        let on_a_file_only = (pawns.0 & !get_file_bb(File::A)) == 0;
+        let on_h_file_only = (pawns.0 & !get_file_bb(File::H)) == 0;
+        if !on_a_file_only && !on_h_file_only {
+            continue;
+        }
+        let queening_file = if on_a_file_only { File::A } else { File::H };
+        let queening_rank = if color == Color::White { Rank::Eighth } else { Rank::First };
+        let queening_sq = Square::make_square(queening_rank, queening_file);
+
+        let bishop_sq = bishops.to_square();
+        if is_dark_square(bishop_sq) == is_dark_square(queening_sq) {
+            continue; // right-colored bishop: this is just a normal endgame
+        }
+
+        let defender_king = board.king_square(!color);
+        let kf = (defender_king.get_file().to_index() as i32 - queening_file.to_index() as i32).abs();
+        let kr = (defender_king.get_rank().to_index() as i32 - queening_rank.to_index() as i32).abs();
+        if kf.max(kr) <= 1 {
+            return Some(SCALE_DRAW);
+        }
+    }
+    None
+}
+
+/// Compute the endgame scale factor (0..=`SCALE_NORMAL`) for `board`,
+/// catching the most common drawn-despite-material-edge shapes: opposite
+/// colored bishops, the wrong rook pawn, insufficient mating material, and a
+/// general fade once neither side has any pawns left.
+fn compute_scale_factor(board: &Board) -> i32 {
+    let white_pawns = (board.pieces(Piece::Pawn) & board.color_combined(Color::White)).popcnt();
+    let black_pawns = (board.pieces(Piece::Pawn) & board.color_combined(Color::Black)).popcnt();
+    let white_knights = (board.pieces(Piece::Knight) & board.color_combined(Color::White)).popcnt();
+    let black_knights = (board.pieces(Piece::Knight) & board.color_combined(Color::Black)).popcnt();
+    let white_bishops = (board.pieces(Piece::Bishop) & board.color_combined(Color::White)).popcnt();
+    let black_bishops = (board.pieces(Piece::Bishop) & board.color_combined(Color::Black)).popcnt();
+    let white_rooks = (board.pieces(Piece::Rook) & board.color_combined(Color::White)).popcnt();
+    let black_rooks = (board.pieces(Piece::Rook) & board.color_combined(Color::Black)).popcnt();
+    let white_queens = (board.pieces(Piece::Queen) & board.color_combined(Color::White)).popcnt();
+    let black_queens = (board.pieces(Piece::Queen) & board.color_combined(Color::Black)).popcnt();
+
+    // Opposite-colored bishops, nothing else on the board.
+    if white_bishops == 1
+        && black_bishops == 1
+        && white_knights == 0
+        && black_knights == 0
+        && white_rooks == 0
+        && black_rooks == 0
+        && white_queens == 0
+        && black_queens == 0
+    {
+        let w_sq = (board.pieces(Piece::Bishop) & board.color_combined(Color::White)).to_square();
+        let b_sq = (board.pieces(Piece::Bishop) & board.color_combined(Color::Black)).to_square();
+        if is_dark_square(w_sq) != is_dark_square(b_sq) {
+            return SCALE_OCB_NO_OTHER_PIECES;
+        }
+    }
+
+    // Insufficient mating material for a pawnless side against a bare king:
+    // KNK, KBK, KNNK can't force mate regardless of the "material" edge.
+    let bare_king = |pawns: u32, knights: u32, bishops: u32, rooks: u32, queens: u32| {
+        pawns == 0 && knights == 0 && bishops == 0 && rooks == 0 && queens == 0
+    };
+    if white_pawns == 0
+        && white_rooks == 0
+        && white_queens == 0
+        && bare_king(black_pawns, black_knights, black_bishops, black_rooks, black_queens)
+        && (white_knights + white_bishops <= 1 || (white_knights == 2 && white_bishops == 0))
+    {
+        return SCALE_DRAW;
+    }
+    if black_pawns == 0
+        && black_rooks == 0
+        && black_queens == 0
+        && bare_king(white_pawns, white_knights, white_bishops, white_rooks, white_queens)
+        && (black_knights + black_bishops <= 1 || (black_knights == 2 && black_bishops == 0))
+    {
+        return SCALE_DRAW;
+    }
+
+    if let Some(scale) = wrong_bishop_rook_pawn_scale(board) {
+        return scale;
+    }
+
+    // General fade toward a draw once neither side has any pawns left to
+    // push: the extreme cases (bare mating material) are already handled
+    // above, so this only nudges the remaining ones down.
+    if white_pawns == 0 && black_pawns == 0 {
+        return SCALE_NORMAL * 3 / 4;
+    }
+
+    SCALE_NORMAL
+}
+
 // ============================================================================
 // HELPER FUNCTIONS
 // ============================================================================
@@ -258,6 +729,45 @@ fn is_passed_pawn(board: &Board, sq: Square, color: Color) -> bool {
     (board.pieces(Piece::Pawn) & board.color_combined(enemy) & blocking_area) == EMPTY
 }
 
+/// Union of diagonal-forward attack squares for every pawn in `pawns`
+/// (computed via shifts rather than per-pawn lookups, since it's only needed
+/// once per color for the mobility area below).
+#[inline]
+fn pawn_attacks_bb(pawns: BitBoard, color: Color) -> BitBoard {
+    let bb = pawns.0;
+    let not_a = !get_file_bb(File::A);
+    let not_h = !get_file_bb(File::H);
+    let attacks = match color {
+        Color::White => ((bb & not_a) << 7) | ((bb & not_h) << 9),
+        Color::Black => ((bb & not_a) >> 9) | ((bb & not_h) >> 7),
+    };
+    BitBoard::new(attacks)
+}
+
+/// Squares that count toward `color`'s mobility: everywhere except its own
+/// pieces and squares an enemy pawn attacks (Stockfish's "mobility area").
+#[inline]
+fn mobility_area(board: &Board, color: Color) -> BitBoard {
+    let own = board.color_combined(color).0;
+    let enemy = !color;
+    let enemy_pawns = board.pieces(Piece::Pawn) & board.color_combined(enemy);
+    let enemy_attacks = pawn_attacks_bb(enemy_pawns, enemy).0;
+    BitBoard::new(!(own | enemy_attacks))
+}
+
+/// The king ring: the king square and its 8 neighbors, extended by one rank
+/// toward the king's own side so pieces that could check it after it shuffles
+/// back a rank are still counted.
+#[inline]
+fn king_ring(king_sq: Square, color: Color) -> BitBoard {
+    let ring = get_king_moves(king_sq) | BitBoard::from_square(king_sq);
+    let shifted = match color {
+        Color::White => BitBoard::new(ring.0 >> 8),
+        Color::Black => BitBoard::new(ring.0 << 8),
+    };
+    ring | shifted
+}
+
 /// Check if a pawn is isolated (no friendly pawns on adjacent files)
 #[inline]
 fn is_isolated_pawn(board: &Board, sq: Square, color: Color) -> bool {
@@ -277,130 +787,288 @@ fn is_isolated_pawn(board: &Board, sq: Square, color: Color) -> bool {
     (board.pieces(Piece::Pawn) & board.color_combined(color) & adj_files) == EMPTY
 }
 
+/// Check if a pawn is opposed (an enemy pawn somewhere ahead on the same file)
+#[inline]
+fn is_opposed_pawn(board: &Board, sq: Square, color: Color) -> bool {
+    let file = sq.get_file();
+    let rank = sq.get_rank();
+    let enemy = !color;
+    let file_bb = BitBoard::new(get_file_bb(file));
+
+    let front_ranks: BitBoard = if color == Color::White {
+        BitBoard::new(!((1u64 << ((rank.to_index() as u8 + 1) * 8)) - 1))
+    } else {
+        BitBoard::new((1u64 << (rank.to_index() as u8 * 8)) - 1)
+    };
+
+    (board.pieces(Piece::Pawn) & board.color_combined(enemy) & file_bb & front_ranks) != EMPTY
+}
+
+/// Check if a pawn is backward: no friendly pawn on an adjacent file is
+/// level with or behind it (so it can't be defended as it pushes), and its
+/// stop square (one square ahead) is covered by an enemy pawn.
+#[inline]
+fn is_backward_pawn(board: &Board, sq: Square, color: Color) -> bool {
+    const FILES: [File; 8] = [File::A, File::B, File::C, File::D, File::E, File::F, File::G, File::H];
+    let file_idx = sq.get_file().to_index();
+    let rank_idx = sq.get_rank().to_index();
+    let own_pawns = board.pieces(Piece::Pawn) & board.color_combined(color);
+
+    for df in [-1i32, 1i32] {
+        let nf = file_idx as i32 + df;
+        if nf < 0 || nf > 7 {
+            continue;
+        }
+        let adj_pawns = own_pawns & BitBoard::new(get_file_bb(FILES[nf as usize]));
+        for adj_sq in adj_pawns {
+            let adj_rank = adj_sq.get_rank().to_index();
+            let behind_or_level = if color == Color::White {
+                adj_rank <= rank_idx
+            } else {
+                adj_rank >= rank_idx
+            };
+            if behind_or_level {
+                return false;
+            }
+        }
+    }
+
+    let stop_rank = if color == Color::White { rank_idx + 1 } else { rank_idx.wrapping_sub(1) };
+    if stop_rank > 7 {
+        return false;
+    }
+    let stop_sq = Square::make_square(Rank::from_index(stop_rank), sq.get_file());
+
+    let enemy = !color;
+    let enemy_pawns = board.pieces(Piece::Pawn) & board.color_combined(enemy);
+    (pawn_attacks_bb(enemy_pawns, enemy) & BitBoard::from_square(stop_sq)) != EMPTY
+}
+
+/// Whether a pawn is defended by an adjacent-file pawn (`supported`) and/or
+/// sits side-by-side with one (`phalanx`).
+#[inline]
+fn pawn_connections(board: &Board, sq: Square, color: Color) -> (bool, bool) {
+    const FILES: [File; 8] = [File::A, File::B, File::C, File::D, File::E, File::F, File::G, File::H];
+    let file_idx = sq.get_file().to_index();
+    let rank_idx = sq.get_rank().to_index();
+    let own_pawns = board.pieces(Piece::Pawn) & board.color_combined(color);
+
+    let mut supported = false;
+    let mut phalanx = false;
+    for df in [-1i32, 1i32] {
+        let nf = file_idx as i32 + df;
+        if nf < 0 || nf > 7 {
+            continue;
+        }
+        let adj_pawns = own_pawns & BitBoard::new(get_file_bb(FILES[nf as usize]));
+        for adj_sq in adj_pawns {
+            let adj_rank = adj_sq.get_rank().to_index();
+            if adj_rank == rank_idx {
+                phalanx = true;
+            }
+            let defends = if color == Color::White {
+                adj_rank + 1 == rank_idx
+            } else {
+                adj_rank == rank_idx + 1
+            };
+            if defends {
+                supported = true;
+            }
+        }
+    }
+    (supported, phalanx)
+}
+
 // ============================================================================
 // MAIN EVALUATION FUNCTION
 // ============================================================================
 
-/// Evaluate the position from white's perspective
+/// Evaluate the position from the side-to-move's perspective, using the
+/// shared default parameter set. This is the stable entry point the rest of
+/// the engine calls; tuning uses [`evaluate_with`] directly with a working
+/// copy of `EvalParams`.
 pub fn evaluate(board: &Board) -> Score {
+    evaluate_with(board, default_params())
+}
+
+/// Evaluate the position from the side-to-move's perspective, using `params`
+/// for every weight.
+fn evaluate_with(board: &Board, params: &EvalParams) -> Score {
     let phase = game_phase(board);
     let mut mg_score: i32 = 0;
     let mut eg_score: i32 = 0;
-    
+
     // Evaluate each color
     for &color in &[Color::White, Color::Black] {
         let sign = if color == Color::White { 1 } else { -1 };
-        
+        let occupied = *board.combined();
+        let mobility_area = mobility_area(board, color);
+
+        // Union of attacked squares per piece type, used below for king safety
+        let mut knight_attacks_all = EMPTY;
+        let mut bishop_attacks_all = EMPTY;
+        let mut rook_attacks_all = EMPTY;
+        let mut queen_attacks_all = EMPTY;
+
         // === MATERIAL AND PST ===
-        
-        // Pawns
+
+        // Pawns (doubled/isolated/passed structure is cached below, keyed
+        // off the pawn-only Zobrist hash, since it's the same for every
+        // node sharing these two pawn bitboards)
         for sq in board.pieces(Piece::Pawn) & board.color_combined(color) {
-            mg_score += sign * PAWN_MG;
-            eg_score += sign * PAWN_EG;
+            mg_score += sign * params.pawn_mg;
+            eg_score += sign * params.pawn_eg;
             let idx = pst_index(sq, color);
-            mg_score += sign * PAWN_PST_MG[idx];
-            eg_score += sign * PAWN_PST_EG[idx];
-            
-            // Pawn structure
-            let file = sq.get_file();
-            
-            // Doubled pawns
-            if pawns_on_file(board, color, file) > 1 {
-                mg_score += sign * DOUBLED_PAWN_PENALTY;
-                eg_score += sign * DOUBLED_PAWN_PENALTY;
-            }
-            
-            // Isolated pawns
-            if is_isolated_pawn(board, sq, color) {
-                mg_score += sign * ISOLATED_PAWN_PENALTY;
-                eg_score += sign * ISOLATED_PAWN_PENALTY;
-            }
-            
-            // Passed pawns
-            if is_passed_pawn(board, sq, color) {
-                let rank = sq.get_rank();
-                let rank_idx = if color == Color::White {
-                    rank as usize
-                } else {
-                    7 - rank as usize
-                };
-                let bonus = PASSED_PAWN_BONUS[rank_idx.min(7)];
-                mg_score += sign * bonus / 2; // Half in midgame
-                eg_score += sign * bonus;     // Full in endgame
-            }
+            mg_score += sign * params.pawn_pst_mg[idx];
+            eg_score += sign * params.pawn_pst_eg[idx];
         }
-        
+
         // Knights
         for sq in board.pieces(Piece::Knight) & board.color_combined(color) {
-            mg_score += sign * KNIGHT_MG;
-            eg_score += sign * KNIGHT_EG;
+            mg_score += sign * params.knight_mg;
+            eg_score += sign * params.knight_eg;
             let idx = pst_index(sq, color);
-            mg_score += sign * KNIGHT_PST_MG[idx];
-            eg_score += sign * KNIGHT_PST_MG[idx]; // Same for EG
+            mg_score += sign * params.knight_pst_mg[idx];
+            eg_score += sign * params.knight_pst_mg[idx]; // Same for EG
+
+            let attacks = get_knight_moves(sq);
+            knight_attacks_all |= attacks;
+            let count = (attacks & mobility_area).popcnt() as usize;
+            let (mob_mg, mob_eg) = params.knight_mobility[count.min(params.knight_mobility.len() - 1)];
+            mg_score += sign * mob_mg;
+            eg_score += sign * mob_eg;
         }
-        
+
         // Bishops
         let bishops = board.pieces(Piece::Bishop) & board.color_combined(color);
         for sq in bishops {
-            mg_score += sign * BISHOP_MG;
-            eg_score += sign * BISHOP_EG;
+            mg_score += sign * params.bishop_mg;
+            eg_score += sign * params.bishop_eg;
             let idx = pst_index(sq, color);
-            mg_score += sign * BISHOP_PST_MG[idx];
-            eg_score += sign * BISHOP_PST_MG[idx];
+            mg_score += sign * params.bishop_pst_mg[idx];
+            eg_score += sign * params.bishop_pst_mg[idx];
+
+            let attacks = get_bishop_moves(sq, occupied);
+            bishop_attacks_all |= attacks;
+            let count = (attacks & mobility_area).popcnt() as usize;
+            let (mob_mg, mob_eg) = params.bishop_mobility[count.min(params.bishop_mobility.len() - 1)];
+            mg_score += sign * mob_mg;
+            eg_score += sign * mob_eg;
         }
         // Bishop pair
         if bishops.popcnt() >= 2 {
-            mg_score += sign * BISHOP_PAIR_BONUS;
-            eg_score += sign * BISHOP_PAIR_BONUS;
+            mg_score += sign * params.bishop_pair_bonus;
+            eg_score += sign * params.bishop_pair_bonus;
         }
-        
+
         // Rooks
         for sq in board.pieces(Piece::Rook) & board.color_combined(color) {
-            mg_score += sign * ROOK_MG;
-            eg_score += sign * ROOK_EG;
+            mg_score += sign * params.rook_mg;
+            eg_score += sign * params.rook_eg;
             let idx = pst_index(sq, color);
-            mg_score += sign * ROOK_PST_MG[idx];
-            eg_score += sign * ROOK_PST_MG[idx];
-            
+            mg_score += sign * params.rook_pst_mg[idx];
+            eg_score += sign * params.rook_pst_mg[idx];
+
+            let attacks = get_rook_moves(sq, occupied);
+            rook_attacks_all |= attacks;
+            let count = (attacks & mobility_area).popcnt() as usize;
+            let (mob_mg, mob_eg) = params.rook_mobility[count.min(params.rook_mobility.len() - 1)];
+            mg_score += sign * mob_mg;
+            eg_score += sign * mob_eg;
+
             let file = sq.get_file();
             let rank = sq.get_rank();
-            
+
             // Open/semi-open file bonus
             if is_open_file(board, file) {
-                mg_score += sign * ROOK_ON_OPEN_FILE;
-                eg_score += sign * ROOK_ON_OPEN_FILE;
+                mg_score += sign * params.rook_on_open_file;
+                eg_score += sign * params.rook_on_open_file;
             } else if is_semi_open_file(board, color, file) {
-                mg_score += sign * ROOK_ON_SEMI_OPEN;
-                eg_score += sign * ROOK_ON_SEMI_OPEN;
+                mg_score += sign * params.rook_on_semi_open;
+                eg_score += sign * params.rook_on_semi_open;
             }
-            
+
             // Rook on 7th rank
             let seventh = if color == Color::White { Rank::Seventh } else { Rank::Second };
             if rank == seventh {
-                mg_score += sign * ROOK_ON_7TH;
-                eg_score += sign * ROOK_ON_7TH;
+                mg_score += sign * params.rook_on_7th;
+                eg_score += sign * params.rook_on_7th;
             }
         }
-        
+
         // Queens
         for sq in board.pieces(Piece::Queen) & board.color_combined(color) {
-            mg_score += sign * QUEEN_MG;
-            eg_score += sign * QUEEN_EG;
+            mg_score += sign * params.queen_mg;
+            eg_score += sign * params.queen_eg;
             let idx = pst_index(sq, color);
-            mg_score += sign * QUEEN_PST_MG[idx];
-            eg_score += sign * QUEEN_PST_MG[idx];
+            mg_score += sign * params.queen_pst_mg[idx];
+            eg_score += sign * params.queen_pst_mg[idx];
+
+            let attacks = get_bishop_moves(sq, occupied) | get_rook_moves(sq, occupied);
+            queen_attacks_all |= attacks;
+            let count = (attacks & mobility_area).popcnt() as usize;
+            let (mob_mg, mob_eg) = params.queen_mobility[count.min(params.queen_mobility.len() - 1)];
+            mg_score += sign * mob_mg;
+            eg_score += sign * mob_eg;
         }
-        
+
+        // === KING SAFETY ===
+        // How much danger `color`'s pieces pose to the enemy king, from
+        // attacks into its ring plus any safe (undefended) check squares.
+        let enemy = !color;
+        let enemy_king_sq = board.king_square(enemy);
+        let ring = king_ring(enemy_king_sq, enemy);
+        let mut king_attack_units = 0;
+
+        let ring_hits = (knight_attacks_all & ring).popcnt() as i32;
+        king_attack_units += params.knight_king_attack_weight * ring_hits;
+        let ring_hits = (bishop_attacks_all & ring).popcnt() as i32;
+        king_attack_units += params.bishop_king_attack_weight * ring_hits;
+        let ring_hits = (rook_attacks_all & ring).popcnt() as i32;
+        king_attack_units += params.rook_king_attack_weight * ring_hits;
+        let ring_hits = (queen_attacks_all & ring).popcnt() as i32;
+        king_attack_units += params.queen_king_attack_weight * ring_hits;
+
+        // Safe checks: squares a piece attacks that would check the enemy
+        // king and aren't defended by that king, landable (not blocked by
+        // one of `color`'s own pieces).
+        let landable = !board.color_combined(color).0;
+        let king_defended = get_king_moves(enemy_king_sq).0;
+        let safe = BitBoard::new(landable & !king_defended);
+
+        let knight_checks = get_knight_moves(enemy_king_sq) & knight_attacks_all & safe;
+        king_attack_units += params.knight_safe_check_penalty * knight_checks.popcnt() as i32;
+        let bishop_checks = get_bishop_moves(enemy_king_sq, occupied) & bishop_attacks_all & safe;
+        king_attack_units += params.bishop_safe_check_penalty * bishop_checks.popcnt() as i32;
+        let rook_checks = get_rook_moves(enemy_king_sq, occupied) & rook_attacks_all & safe;
+        king_attack_units += params.rook_safe_check_penalty * rook_checks.popcnt() as i32;
+        let queen_check_sqs = get_bishop_moves(enemy_king_sq, occupied) | get_rook_moves(enemy_king_sq, occupied);
+        let queen_checks = queen_check_sqs & queen_attacks_all & safe;
+        king_attack_units += params.queen_safe_check_penalty * queen_checks.popcnt() as i32;
+
+        // Danger grows quadratically with attack units, midgame only (fades
+        // out naturally via taper as it's only added to mg_score).
+        mg_score += sign * (king_attack_units * king_attack_units / 4096);
+
         // King
         let king_sq = board.king_square(color);
         let idx = pst_index(king_sq, color);
-        mg_score += sign * KING_PST_MG[idx];
-        eg_score += sign * KING_PST_EG[idx];
+        mg_score += sign * params.king_pst_mg[idx];
+        eg_score += sign * params.king_pst_eg[idx];
     }
-    
+
+    // === PAWN STRUCTURE (cached) ===
+    let pawn_info = probe_pawn_structure(board, params);
+    mg_score += pawn_info.mg;
+    eg_score += pawn_info.eg;
+
+    // === ENDGAME SCALE FACTOR ===
+    let scale = compute_scale_factor(board);
+    eg_score = eg_score * scale / SCALE_NORMAL;
+
     // Taper the score
-    let final_score = taper(eg_score, mg_score, phase);
-    
+    let final_score = taper(mg_score, eg_score, phase);
+
     // Return from side-to-move perspective
     if board.side_to_move() == Color::White {
         Score::cp(final_score)
@@ -409,11 +1077,112 @@ pub fn evaluate(board: &Board) -> Score {
     }
 }
 
+// ============================================================================
+// TEXEL-STYLE TUNING
+// ============================================================================
+
+/// Texel-style tuning: fit the evaluation's sigmoid scaling constant and
+/// then coordinate-descend every weight in [`EvalParams`] against a labeled
+/// set of positions.
+pub mod tuning {
+    use super::{clear_pawn_hash, evaluate_with, Color, EvalParams};
+    use crate::types::Board;
+
+    /// A single training example: a position and its game result from
+    /// White's perspective (0.0 = Black win, 0.5 = draw, 1.0 = White win).
+    pub struct Sample {
+        pub board: Board,
+        pub result: f64,
+    }
+
+    /// Maps a White-relative centipawn score to a win probability, Texel
+    /// style: `1 / (1 + 10^(-k * score / 400))`.
+    fn sigmoid(score: f64, k: f64) -> f64 {
+        1.0 / (1.0 + 10f64.powf(-k * score / 400.0))
+    }
+
+    /// White-relative centipawn score for `board` under `params`.
+    fn white_relative_score(board: &Board, params: &EvalParams) -> f64 {
+        let score = evaluate_with(board, params).raw() as f64;
+        if board.side_to_move() == Color::White { score } else { -score }
+    }
+
+    /// Mean squared error between the sigmoid-mapped evaluation and each
+    /// sample's game result, at a given scaling constant `k`.
+    fn mse(samples: &[Sample], params: &EvalParams, k: f64) -> f64 {
+        samples
+            .iter()
+            .map(|s| {
+                let err = sigmoid(white_relative_score(&s.board, params), k) - s.result;
+                err * err
+            })
+            .sum::<f64>()
+            / samples.len() as f64
+    }
+
+    /// Fit the sigmoid scaling constant `k` by coordinate descent: start
+    /// from 1.0 and shrink the step whenever neither direction improves the
+    /// error, until the step is negligible.
+    pub fn fit_k(samples: &[Sample], params: &EvalParams) -> f64 {
+        let mut k = 1.0;
+        let mut step = 1.0;
+        let mut best = mse(samples, params, k);
+        while step > 0.001 {
+            let mut improved = false;
+            for candidate in [k + step, k - step] {
+                if candidate <= 0.0 {
+                    continue;
+                }
+                let err = mse(samples, params, candidate);
+                if err < best {
+                    best = err;
+                    k = candidate;
+                    improved = true;
+                }
+            }
+            if !improved {
+                step /= 2.0;
+            }
+        }
+        k
+    }
+
+    /// Gradient-free coordinate descent over every scalar weight in
+    /// `params`: fit `k` once up front, then repeatedly nudge each weight by
+    /// +1 and -1, keeping whichever change reduces the mean squared error
+    /// against `samples`, until a full pass over every weight makes no
+    /// improvement.
+    pub fn tune(params: &mut EvalParams, samples: &[Sample]) {
+        let k = fit_k(samples, params);
+        let mut best = mse(samples, params, k);
+        loop {
+            let mut improved = false;
+            for i in 0..params.scalars_mut().len() {
+                for delta in [1, -1] {
+                    *params.scalars_mut()[i] += delta;
+                    clear_pawn_hash();
+                    let err = mse(samples, params, k);
+                    if err < best {
+                        best = err;
+                        improved = true;
+                    } else {
+                        *params.scalars_mut()[i] -= delta;
+                        clear_pawn_hash();
+                    }
+                }
+            }
+            if !improved {
+                break;
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::str::FromStr;
-    
+
     #[test]
     fn test_starting_position() {
         let board = Board::default();
@@ -430,4 +1199,84 @@ mod tests {
         // White should have big advantage
         assert!(score.raw() > 800);
     }
+
+    #[test]
+    fn test_evaluate_with_default_params_matches_evaluate() {
+        let board = Board::default();
+        assert_eq!(evaluate(&board), evaluate_with(&board, &EvalParams::default()));
+    }
+
+    #[test]
+    fn test_scalars_mut_covers_every_field() {
+        // material (10) + PSTs (8*64) + pawn/rook bonuses (34, including the
+        // doubled (mg,eg) pairs in connected_pawn_bonus) + mobility tables
+        // ((9+14+15+28)*2) + king safety weights (4+4).
+        let mut params = EvalParams::default();
+        assert_eq!(params.scalars_mut().len(), 10 + 8 * 64 + 34 + 132 + 8);
+    }
+
+    #[test]
+    fn test_tune_does_not_increase_error_on_a_tiny_dataset() {
+        use tuning::Sample;
+
+        let samples = vec![
+            Sample { board: Board::default(), result: 0.5 },
+            Sample {
+                board: Board::from_str("rnb1kbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap(),
+                result: 1.0,
+            },
+        ];
+
+        let mut params = EvalParams::default();
+        let k = tuning::fit_k(&samples, &params);
+        let before = samples
+            .iter()
+            .map(|s| {
+                let score = if s.board.side_to_move() == Color::White {
+                    evaluate_with(&s.board, &params).raw() as f64
+                } else {
+                    -(evaluate_with(&s.board, &params).raw() as f64)
+                };
+                let err = 1.0 / (1.0 + 10f64.powf(-k * score / 400.0)) - s.result;
+                err * err
+            })
+            .sum::<f64>()
+            / samples.len() as f64;
+
+        tuning::tune(&mut params, &samples);
+
+        let after = samples
+            .iter()
+            .map(|s| {
+                let score = if s.board.side_to_move() == Color::White {
+                    evaluate_with(&s.board, &params).raw() as f64
+                } else {
+                    -(evaluate_with(&s.board, &params).raw() as f64)
+                };
+                let err = 1.0 / (1.0 + 10f64.powf(-k * score / 400.0)) - s.result;
+                err * err
+            })
+            .sum::<f64>()
+            / samples.len() as f64;
+
+        assert!(after <= before);
+    }
+
+    #[test]
+    fn test_opposite_colored_bishops_reduce_score_below_same_colored() {
+        // Identical material (White up two pawns), only the bishops' square
+        // colors differ: c8/c1 are opposite colors, b8/c1 are the same.
+        let ocb = Board::from_str("2b1k3/8/8/8/8/8/PP2K3/2B5 w - - 0 1").unwrap();
+        let same_colored = Board::from_str("1b2k3/8/8/8/8/8/PP2K3/2B5 w - - 0 1").unwrap();
+        assert!(evaluate(&ocb).raw() < evaluate(&same_colored).raw());
+    }
+
+    #[test]
+    fn test_insufficient_mating_material_is_near_draw() {
+        // White has two knights and no pawns against a bare king: KNNK can't
+        // be forced to mate, so the scale factor should zero out eg_score
+        // and leave only a small mg-phase residue.
+        let board = Board::from_str("4k3/8/8/8/8/8/3NN3/4K3 w - - 0 1").unwrap();
+        assert!(evaluate(&board).raw().abs() < 300);
+    }
 }
@@ -1,58 +1,174 @@
 //! Board evaluation module.
 //!
 //! Provides static evaluation of chess positions.
-//! Currently uses simple material counting.
-//! Designed for easy extension to NNUE evaluation.
+//! Falls back to the tapered hand-crafted evaluation (`hce`) when no NNUE
+//! network is loaded. See `SearchEvaluator` for the incremental evaluator
+//! used inside search.
 
-use crate::types::{Board, Score, Color, Piece, piece_value, Value};
+mod hce;
+pub mod nnue;
+
+use crate::types::{Board, Color, Score, Move};
+use nnue::{Model, NnueEvaluator};
+
+pub use hce::{tuning, EvalParams};
 
 /// Evaluate the position from the side-to-move's perspective.
 ///
-/// Returns a score in centipawns.
-/// Positive = good for side to move, negative = bad.
+/// Returns a score in centipawns, using the tapered hand-crafted evaluation
+/// (material, PST, mobility, king safety and pawn structure, interpolated by
+/// game phase and scaled for drawish endgames).
 pub fn evaluate(board: &Board) -> Score {
-    let eval = material_eval(board);
-    
-    // Convert to side-to-move perspective
-    let score = if board.side_to_move() == Color::White {
-        eval
-    } else {
-        -eval
-    };
-
-    Score::cp(score)
+    hce::evaluate(board)
+}
+
+/// Scale (numerator) for `compute_optimism`'s `k * prev_score / (|prev_score| + c)` curve.
+const OPTIMISM_K: i32 = 118;
+/// Offset (denominator bias) for `compute_optimism`'s curve.
+const OPTIMISM_C: i32 = 169;
+
+/// Derive the root's optimism term from the previous iterative-deepening
+/// iteration's score, following Stockfish's `k * prev / (|prev| + c)` curve:
+/// it saturates quickly as `prev_score` grows, so a decisive advantage
+/// doesn't push optimism far past `k`. Returns zero when no prior score is
+/// available (the first iteration, or after a position change).
+pub fn compute_optimism(prev_score: Option<Score>) -> Score {
+    match prev_score {
+        Some(s) if !s.is_mate_score() => {
+            let v = s.raw();
+            Score::cp(OPTIMISM_K * v / (v.abs() + OPTIMISM_C))
+        }
+        _ => Score::cp(0),
+    }
+}
+
+enum Kind<'m> {
+    Nnue(NnueEvaluator<'m>),
+    Fallback,
 }
 
-/// Simple material evaluation (white's perspective)
-fn material_eval(board: &Board) -> Value {
-    let mut score: Value = 0;
+/// Incremental evaluator used inside search.
+///
+/// Wraps the NNUE accumulator when a network is loaded, and falls back to
+/// `evaluate` (tapered HCE) otherwise. Cloning an `Nnue` variant copies its
+/// accumulator state and finny table (but not the loaded network weights);
+/// `apply_move`/`revert_move` avoid that copy.
+pub struct SearchEvaluator<'m> {
+    kind: Kind<'m>,
+    /// Root-relative optimism bonus (see `compute_optimism`), set once per
+    /// iterative-deepening iteration and carried into every child evaluator
+    /// via `clone`. Added to the static eval with `root_color`'s sign so the
+    /// engine leans into activity/risk while it believes it's winning, and
+    /// toward complications while it believes it's losing.
+    optimism: Score,
+    /// Side to move at the root, used to flip `optimism`'s sign for nodes
+    /// where the opponent is to move.
+    root_color: Color,
+}
 
-    for piece in &[Piece::Pawn, Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen] {
-        let white_pieces = board.pieces(*piece) & board.color_combined(Color::White);
-        let black_pieces = board.pieces(*piece) & board.color_combined(Color::Black);
+impl<'m> SearchEvaluator<'m> {
+    /// Create an evaluator for the root position. Uses NNUE when `model` is
+    /// `Some`, otherwise falls back to the classical evaluation.
+    pub fn new(model: Option<&'m Model>, board: &Board) -> Self {
+        let kind = match model {
+            Some(m) => Kind::Nnue(NnueEvaluator::new(&m.model, board)),
+            None => Kind::Fallback,
+        };
+        Self {
+            kind,
+            optimism: Score::cp(0),
+            root_color: board.side_to_move(),
+        }
+    }
+
+    /// Set the root-relative optimism bonus for the upcoming search (see
+    /// `compute_optimism`). Takes effect on every `evaluate` call from here
+    /// on, including in clones made for child nodes.
+    pub fn set_optimism(&mut self, optimism: Score) {
+        self.optimism = optimism;
+    }
+
+    /// Evaluate the current position from the side-to-move's perspective,
+    /// biased by the root-relative `optimism` term.
+    #[inline]
+    pub fn evaluate(&mut self, board: &Board) -> Score {
+        let eval = match &mut self.kind {
+            Kind::Nnue(e) => e.evaluate(board.side_to_move()),
+            Kind::Fallback => evaluate(board),
+        };
+        if board.side_to_move() == self.root_color {
+            eval + self.optimism
+        } else {
+            eval - self.optimism
+        }
+    }
+
+    /// Incrementally update for a move about to be made from `board`.
+    /// Returns `false` if a full refresh is needed (no piece on the move's
+    /// source square); callers should call `refresh` in that case. King
+    /// moves are handled in place, cold finny-table slots included.
+    #[inline]
+    pub fn update_move(&mut self, board: &Board, mv: Move) -> bool {
+        match &mut self.kind {
+            Kind::Nnue(e) => e.update_move(board, mv),
+            Kind::Fallback => true,
+        }
+    }
+
+    /// Rebuild the evaluator state from scratch for `board`.
+    #[inline]
+    pub fn refresh(&mut self, board: &Board) {
+        if let Kind::Nnue(e) = &mut self.kind {
+            e.refresh(board);
+        }
+    }
 
-        let white_count = white_pieces.popcnt() as Value;
-        let black_count = black_pieces.popcnt() as Value;
+    /// Incrementally apply `mv` (played from `board`, the position *before*
+    /// the move) in place, returning a snapshot `revert_move` restores.
+    /// `new_board` is the position *after* the move, used only if a full
+    /// refresh turns out to be necessary. Lets recursive search (see
+    /// `search::qsearch`) mutate one evaluator per move instead of cloning a
+    /// fresh one at every node.
+    #[inline]
+    pub fn apply_move(&mut self, board: &Board, mv: Move, new_board: &Board) -> EvalUndo<'m> {
+        match &mut self.kind {
+            Kind::Nnue(e) => EvalUndo::Nnue(e.apply_move(board, mv, new_board)),
+            Kind::Fallback => EvalUndo::Fallback,
+        }
+    }
 
-        score += piece_value(*piece) * (white_count - black_count);
+    /// Undo the effect of the `apply_move` call that produced `undo`. Undos
+    /// must be applied in LIFO order relative to their `apply_move` calls.
+    #[inline]
+    pub fn revert_move(&mut self, undo: EvalUndo<'m>) {
+        match (&mut self.kind, undo) {
+            (Kind::Nnue(e), EvalUndo::Nnue(u)) => e.revert_move(u),
+            (Kind::Fallback, EvalUndo::Fallback) => {}
+            _ => unreachable!("EvalUndo must come from the same evaluator's apply_move"),
+        }
     }
+}
 
-    score
+/// Snapshot returned by `SearchEvaluator::apply_move`, consumed by the
+/// matching `revert_move`.
+pub enum EvalUndo<'m> {
+    Nnue(nnue::NnueUndo<'m>),
+    Fallback,
 }
 
-// === Future: NNUE Evaluation ===
-// pub struct NnueEvaluator {
-//     model: nnue::stockfish::halfkp::SfHalfKpModel,
-//     state: Option<nnue::stockfish::halfkp::SfHalfKpState>,
-// }
-//
-// impl NnueEvaluator {
-//     pub fn evaluate(&mut self, board: &Board) -> Score {
-//         // Build NNUE state from board
-//         // Call activate()
-//         // Scale output to centipawns
-//     }
-// }
+impl<'m> Clone for SearchEvaluator<'m> {
+    fn clone(&self) -> Self {
+        let kind = match &self.kind {
+            Kind::Nnue(e) => Kind::Nnue(e.clone()),
+            Kind::Fallback => Kind::Fallback,
+        };
+        Self {
+            kind,
+            optimism: self.optimism,
+            root_color: self.root_color,
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -75,4 +191,38 @@ mod tests {
         // White should be significantly ahead
         assert!(score.raw() > 800);
     }
+
+    #[test]
+    fn test_compute_optimism_no_prev_score() {
+        assert_eq!(compute_optimism(None), Score::cp(0));
+    }
+
+    #[test]
+    fn test_compute_optimism_sign_matches_prev_score() {
+        assert!(compute_optimism(Some(Score::cp(200))).raw() > 0);
+        assert!(compute_optimism(Some(Score::cp(-200))).raw() < 0);
+    }
+
+    #[test]
+    fn test_compute_optimism_ignores_mate_scores() {
+        assert_eq!(compute_optimism(Some(Score::mate_in(3))), Score::cp(0));
+    }
+
+    #[test]
+    fn test_evaluate_applies_optimism_with_flipped_sign_for_opponent() {
+        let board = Board::default();
+        let mut evaluator = SearchEvaluator::new(None, &board);
+        let baseline = evaluate(&board);
+
+        evaluator.set_optimism(Score::cp(30));
+        assert_eq!(evaluator.evaluate(&board), baseline + Score::cp(30));
+
+        let after_e4 = board.make_move_new(chess::ChessMove::new(
+            chess::Square::E2,
+            chess::Square::E4,
+            None,
+        ));
+        let opponent_baseline = evaluate(&after_e4);
+        assert_eq!(evaluator.evaluate(&after_e4), opponent_baseline - Score::cp(30));
+    }
 }
@@ -1,17 +1,40 @@
 //! NNUE wrapper for `nnue-rs` with incremental update support.
 //!
-//! Uses forked nnue-rs with exposed state for efficient incremental updates.
+//! Uses forked nnue-rs with exposed state for efficient incremental
+//! updates: besides the `add`/`sub`/`update_king` feature-level API, the
+//! fork exposes `SfHalfKpState::accumulator`/`set_accumulator` to read and
+//! write one perspective's raw accumulator directly, which `FinnyTable`
+//! below relies on to restore a cached accumulator without touching the
+//! other perspective.
 
 use crate::types::{Board, Score, ToNnue, Move};
 use nnue::stockfish::halfkp::{SfHalfKpFullModel, SfHalfKpModel, SfHalfKpState};
+use sha2::{Digest, Sha256};
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, Cursor, Read};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use binread::BinRead;
 
 /// Global type for shared thread-safe model
 pub type Model = Arc<SfHalfKpFullModel>;
 
+/// Name of the net `resolve_model` falls back to when the caller doesn't
+/// supply an explicit path. The `nn-<12 hex chars>` prefix is the truncated
+/// SHA-256 of the net's contents, so `verify_net_hash` can check a
+/// downloaded copy without a separate checksum file.
+pub const DEFAULT_NET_NAME: &str = "nn-6c5aa697e62d.nnue";
+
+/// Where `download_and_verify_net` fetches `DEFAULT_NET_NAME` from if it
+/// isn't embedded and isn't already in the local cache.
+const DEFAULT_NET_URL_BASE: &str = "https://github.com/TheChii/chessgo-nets/releases/download/latest";
+
+/// The default net embedded directly in the binary. Only present when built
+/// with the `embedded-net` feature (off by default, since it adds several
+/// MB to every binary regardless of whether the user ever needs it).
+#[cfg(feature = "embedded-net")]
+static EMBEDDED_NET: &[u8] = include_bytes!(concat!("../../nets/", "nn-6c5aa697e62d.nnue"));
+
 /// Load NNUE model from file
 pub fn load_model(path: &str) -> std::io::Result<Model> {
     let file = File::open(path)?;
@@ -22,6 +45,119 @@ pub fn load_model(path: &str) -> std::io::Result<Model> {
     }
 }
 
+fn load_model_bytes(bytes: &[u8]) -> std::io::Result<Model> {
+    let mut reader = Cursor::new(bytes);
+    match SfHalfKpFullModel::read(&mut reader) {
+        Ok(model) => Ok(Arc::new(model)),
+        Err(e) => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+    }
+}
+
+/// Resolve and load the net to use, in priority order:
+///
+/// 1. `explicit_path`, if given (a user- or GUI-supplied `EvalFile` value) —
+///    loaded as-is, no hash check, since the user vouched for it.
+/// 2. The net embedded in the binary, if built with the `embedded-net`
+///    feature.
+/// 3. `DEFAULT_NET_NAME` from the local cache directory, downloading and
+///    hash-verifying it first if it isn't cached yet.
+///
+/// Returns the loaded model along with the name it was resolved to, so the
+/// caller can report it back over UCI (`option name EvalFile`).
+pub fn resolve_model(explicit_path: Option<&str>) -> std::io::Result<(Model, String)> {
+    if let Some(path) = explicit_path {
+        let model = load_model(path)?;
+        return Ok((model, path.to_string()));
+    }
+
+    #[cfg(feature = "embedded-net")]
+    {
+        let model = load_model_bytes(EMBEDDED_NET)?;
+        Ok((model, DEFAULT_NET_NAME.to_string()))
+    }
+
+    #[cfg(not(feature = "embedded-net"))]
+    {
+        let cached = cached_net_path(DEFAULT_NET_NAME)?;
+        if !cached.exists() {
+            download_and_verify_net(DEFAULT_NET_NAME, &cached)?;
+        }
+        let path = cached.to_str().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "non-UTF8 net cache path")
+        })?;
+        let model = load_model(path)?;
+        Ok((model, DEFAULT_NET_NAME.to_string()))
+    }
+}
+
+/// Local cache directory for downloaded nets
+/// (`$XDG_CACHE_HOME/chessgo`, falling back to `~/.cache/chessgo`, then
+/// `./.chessgo-cache` if no home directory can be found).
+#[cfg(not(feature = "embedded-net"))]
+fn cache_dir() -> std::io::Result<PathBuf> {
+    let dir = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .map(|base| base.join("chessgo"))
+        .unwrap_or_else(|| PathBuf::from(".chessgo-cache"));
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+#[cfg(not(feature = "embedded-net"))]
+fn cached_net_path(name: &str) -> std::io::Result<PathBuf> {
+    Ok(cache_dir()?.join(name))
+}
+
+/// Download `name` into `dest`, verifying its truncated SHA-256 matches the
+/// hash encoded in the filename before accepting it — a corrupt download or
+/// a tampered mirror is an error, not a silent fallback to a wrong net.
+#[cfg(not(feature = "embedded-net"))]
+fn download_and_verify_net(name: &str, dest: &Path) -> std::io::Result<()> {
+    let url = format!("{DEFAULT_NET_URL_BASE}/{name}");
+    let mut bytes = Vec::new();
+    ureq::get(&url)
+        .call()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?
+        .into_reader()
+        .read_to_end(&mut bytes)?;
+
+    verify_net_hash(name, &bytes)?;
+
+    // Write to a temp file first and rename into place, so a crash or a
+    // concurrent reader never observes a partially-written net.
+    let tmp = dest.with_extension("part");
+    std::fs::write(&tmp, &bytes)?;
+    std::fs::rename(&tmp, dest)?;
+    Ok(())
+}
+
+/// Check that `name` (of the form `nn-<12 hex chars>.nnue`) matches the
+/// truncated SHA-256 of `bytes`.
+#[cfg(not(feature = "embedded-net"))]
+fn verify_net_hash(name: &str, bytes: &[u8]) -> std::io::Result<()> {
+    let expected = name
+        .strip_prefix("nn-")
+        .and_then(|s| s.strip_suffix(".nnue"))
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("net filename `{name}` doesn't encode a hash"),
+            )
+        })?;
+
+    let digest = Sha256::digest(bytes);
+    let actual: String = digest.iter().take(6).map(|b| format!("{b:02x}")).collect();
+
+    if actual != expected {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("net `{name}` hash mismatch: expected {expected}, got {actual}"),
+        ));
+    }
+    Ok(())
+}
+
 /// Create a fresh NNUE state from a board position
 pub fn create_state<'m>(model: &'m SfHalfKpModel, board: &Board) -> SfHalfKpState<'m> {
     let white_king = board.king_square(chess::Color::White).to_nnue();
@@ -82,11 +218,128 @@ fn refresh_side_accumulator(state: &mut SfHalfKpState<'_>, board: &Board, perspe
     }
 }
 
+/// Piece types tracked by a finny-table bitboard snapshot (kings aren't
+/// features in HalfKP, so they're excluded).
+const FINNY_PIECES: [chess::Piece; 5] = [
+    chess::Piece::Pawn,
+    chess::Piece::Knight,
+    chess::Piece::Bishop,
+    chess::Piece::Rook,
+    chess::Piece::Queen,
+];
+
+/// Non-king piece bitboards, as `[piece_idx][color_idx]` raw `u64`s, for
+/// diffing against a cached finny-table entry.
+fn finny_bitboards(board: &Board) -> [[u64; 2]; 5] {
+    let mut bb = [[0u64; 2]; 5];
+    for (i, &piece) in FINNY_PIECES.iter().enumerate() {
+        for (c, &color) in [chess::Color::White, chess::Color::Black].iter().enumerate() {
+            bb[i][c] = (board.pieces(piece) & board.color_combined(color)).0;
+        }
+    }
+    bb
+}
+
+/// One slot of the accumulator-refresh cache ("finny table"): a previously
+/// built accumulator for a given king square, plus the non-king piece
+/// bitboards that produced it.
+#[derive(Clone)]
+struct FinnyEntry {
+    bitboards: [[u64; 2]; 5],
+    accumulator: Vec<i16>,
+}
+
+/// Per-(perspective, king-square) cache of refresh accumulators.
+///
+/// A king move normally forces a full rebuild of the mover's accumulator,
+/// since every HalfKP feature for that side is indexed by its own king
+/// square. A finny table turns that into an O(changed pieces) update
+/// instead: landing on a king square this table has seen before reuses the
+/// accumulator it cached for that square and only applies the delta
+/// against the pieces that have moved since, rather than re-deriving the
+/// whole accumulator from scratch. An empty (cold) slot falls back to the
+/// existing full rebuild and then populates itself from the result.
+pub struct FinnyTable {
+    /// `[perspective_idx][king_square_idx]`
+    slots: Box<[[Option<FinnyEntry>; 64]; 2]>,
+}
+
+impl FinnyTable {
+    pub fn new() -> Self {
+        Self {
+            slots: Box::new(std::array::from_fn(|_| std::array::from_fn(|_| None))),
+        }
+    }
+
+    fn perspective_index(perspective: nnue::Color) -> usize {
+        match perspective {
+            nnue::Color::White => 0,
+            nnue::Color::Black => 1,
+        }
+    }
+}
+
+impl Default for FinnyTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for FinnyTable {
+    fn clone(&self) -> Self {
+        Self { slots: self.slots.clone() }
+    }
+}
+
+/// Refresh `perspective`'s accumulator in `state` for its king now on
+/// `king_sq` (already applied via `state.update_king`), using `finny` to
+/// avoid a full rebuild when this king square has been seen before.
+fn refresh_side_via_finny(
+    state: &mut SfHalfKpState<'_>,
+    finny: &mut FinnyTable,
+    board: &Board,
+    perspective: nnue::Color,
+    king_sq: chess::Square,
+) {
+    let current_bb = finny_bitboards(board);
+    let p = FinnyTable::perspective_index(perspective);
+    let s = king_sq.to_index();
+
+    if let Some(entry) = finny.slots[p][s].clone() {
+        // Warm slot: seed the accumulator from the cache, then touch only
+        // the pieces that differ from the board that produced it.
+        state.set_accumulator(perspective, &entry.accumulator);
+        for (i, &piece) in FINNY_PIECES.iter().enumerate() {
+            for (c, &color) in [chess::Color::White, chess::Color::Black].iter().enumerate() {
+                let added = current_bb[i][c] & !entry.bitboards[i][c];
+                let removed = entry.bitboards[i][c] & !current_bb[i][c];
+                let nnue_piece = piece.to_nnue();
+                let nnue_color = color.to_nnue();
+                for sq in chess::BitBoard::new(removed) {
+                    state.sub(perspective, nnue_piece, nnue_color, sq.to_nnue());
+                }
+                for sq in chess::BitBoard::new(added) {
+                    state.add(perspective, nnue_piece, nnue_color, sq.to_nnue());
+                }
+            }
+        }
+    } else {
+        // Cold slot: fall back to the full rebuild, then populate below.
+        refresh_side_accumulator(state, board, perspective);
+    }
+
+    finny.slots[p][s] = Some(FinnyEntry {
+        bitboards: current_bb,
+        accumulator: state.accumulator(perspective).to_vec(),
+    });
+}
+
 /// Update state for a move (incremental)
 /// Returns true if update succeeded, false if full refresh needed
 #[inline]
 pub fn update_state_for_move(
     state: &mut SfHalfKpState<'_>,
+    finny: &mut FinnyTable,
     board: &Board,  // Position BEFORE the move
     mv: Move,
 ) -> bool {
@@ -126,9 +379,10 @@ pub fn update_state_for_move(
         
         // Create a temporary board with the move applied to rebuild active side
         let new_board = board.make_move_new(mv);
-        
-        // Refresh the active side's accumulator with all pieces
-        refresh_side_accumulator(state, &new_board, active);
+
+        // Refresh the active side's accumulator, reusing the finny-table
+        // entry for `to` if this king square is warm.
+        refresh_side_via_finny(state, finny, &new_board, active, to);
         
         // Handle castling: rook also moves (rook IS a feature)
         let is_castling = (from.get_file() == chess::File::E) 
@@ -223,6 +477,8 @@ pub fn refresh_state<'m>(state: &mut SfHalfKpState<'m>, model: &'m SfHalfKpModel
 pub struct NnueEvaluator<'m> {
     model: &'m SfHalfKpModel,
     state: SfHalfKpState<'m>,
+    /// Accumulator-refresh cache for king moves (see `FinnyTable`).
+    finny: FinnyTable,
 }
 
 impl<'m> NnueEvaluator<'m> {
@@ -231,6 +487,7 @@ impl<'m> NnueEvaluator<'m> {
         Self {
             model,
             state: create_state(model, board),
+            finny: FinnyTable::new(),
         }
     }
 
@@ -243,7 +500,7 @@ impl<'m> NnueEvaluator<'m> {
     /// Update for a move, returns false if refresh needed
     #[inline]
     pub fn update_move(&mut self, board: &Board, mv: Move) -> bool {
-        update_state_for_move(&mut self.state, board, mv)
+        update_state_for_move(&mut self.state, &mut self.finny, board, mv)
     }
 
     /// Refresh state for a new position (after king move or when needed)
@@ -263,6 +520,35 @@ impl<'m> NnueEvaluator<'m> {
     pub fn restore_state(&mut self, state: SfHalfKpState<'m>) {
         self.state = state;
     }
+
+    /// Incrementally apply `mv` (played from `board`, the position *before*
+    /// the move) in place, falling back to a full `refresh` from
+    /// `new_board` (the position *after* the move) if the incremental path
+    /// can't handle it (no piece on the move's source square; king moves,
+    /// cold finny-table slots included, are handled incrementally).
+    /// Returns a snapshot `revert_move` can restore, so a whole recursive
+    /// search doesn't need to clone the evaluator (and its finny table) per
+    /// move the way `Clone` does.
+    #[inline]
+    pub fn apply_move(&mut self, board: &Board, mv: Move, new_board: &Board) -> NnueUndo<'m> {
+        let undo = NnueUndo { state: self.clone_state() };
+        if !self.update_move(board, mv) {
+            self.refresh(new_board);
+        }
+        undo
+    }
+
+    /// Undo the effect of the `apply_move` call that produced `undo`.
+    #[inline]
+    pub fn revert_move(&mut self, undo: NnueUndo<'m>) {
+        self.restore_state(undo.state);
+    }
+}
+
+/// Snapshot returned by `NnueEvaluator::apply_move`, consumed by the
+/// matching `revert_move`.
+pub struct NnueUndo<'m> {
+    state: SfHalfKpState<'m>,
 }
 
 impl<'m> Clone for NnueEvaluator<'m> {
@@ -270,6 +556,99 @@ impl<'m> Clone for NnueEvaluator<'m> {
         Self {
             model: self.model,
             state: self.state.clone(),
+            finny: self.finny.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Piece, Square};
+    use std::str::FromStr;
+
+    /// Loads the net pointed to by `CHESSGO_TEST_NET`, if set. Real net
+    /// weights are multi-megabyte binaries (see `DEFAULT_NET_URL_BASE`)
+    /// that don't belong checked into the repo, so unlike the rest of the
+    /// test suite these can't embed their own fixture; run with
+    /// `CHESSGO_TEST_NET=/path/to/net.nnue cargo test -- --ignored` once one
+    /// is available locally.
+    fn test_model() -> Option<Model> {
+        let path = std::env::var("CHESSGO_TEST_NET").ok()?;
+        Some(load_model(&path).expect("CHESSGO_TEST_NET should point to a valid net"))
+    }
+
+    /// Asserts that `evaluator`'s incremental state agrees with a full
+    /// rebuild (`evaluate_scratch`) of `board`.
+    fn assert_matches_scratch(evaluator: &mut NnueEvaluator<'_>, model: &SfHalfKpModel, board: &Board) {
+        assert_eq!(
+            evaluator.evaluate(board.side_to_move()),
+            evaluate_scratch(model, board),
+        );
+    }
+
+    /// Walks a king move that warms a finny-table slot, a later king move
+    /// back onto that same (now-warm) slot, and a capture, checking that
+    /// `apply_move`'s incremental path agrees with a from-scratch rebuild
+    /// after every step. This is the same cross-check chunk8's mate/TB-band
+    /// arithmetic got unit tests for — incremental accumulator diffing is
+    /// just as easy to get subtly wrong, and wrong output there is silent,
+    /// not a panic.
+    #[test]
+    #[ignore = "requires a real net file via CHESSGO_TEST_NET"]
+    fn test_incremental_king_move_and_capture_match_scratch() {
+        let Some(model) = test_model() else { return };
+        let model = &model.model;
+
+        let mut board = Board::default();
+        let mut evaluator = NnueEvaluator::new(model, &board);
+
+        let moves = [
+            Move::new(Square::E2, Square::E4, None),
+            Move::new(Square::E7, Square::E5, None),
+            Move::new(Square::G1, Square::F3, None),
+            Move::new(Square::B8, Square::C6, None),
+            Move::new(Square::F1, Square::C4, None),
+            Move::new(Square::G8, Square::F6, None),
+            Move::new(Square::E1, Square::G1, None), // castles: king onto g1 (cold finny slot)
+            Move::new(Square::F6, Square::E4, None), // capture
+            Move::new(Square::G1, Square::H1, None), // king walks off g1
+            Move::new(Square::D7, Square::D6, None),
+            Move::new(Square::H1, Square::G1, None), // ...and back onto g1 (now warm)
+        ];
+
+        let mut last_undo = None;
+        let mut before_last = None;
+        for mv in moves {
+            let new_board = board.make_move_new(mv);
+            before_last = Some(board);
+            last_undo = Some(evaluator.apply_move(&board, mv, &new_board));
+            board = new_board;
+            assert_matches_scratch(&mut evaluator, model, &board);
+        }
+
+        // `revert_move` on the final move must also land back on a
+        // from-scratch match of the position just before it, not just
+        // leave the post-move path looking right.
+        evaluator.revert_move(last_undo.unwrap());
+        assert_matches_scratch(&mut evaluator, model, &before_last.unwrap());
+    }
+
+    /// A pawn promoting to a queen must update the incremental accumulator
+    /// the same way a full rebuild would.
+    #[test]
+    #[ignore = "requires a real net file via CHESSGO_TEST_NET"]
+    fn test_incremental_promotion_matches_scratch() {
+        let Some(model) = test_model() else { return };
+        let model = &model.model;
+
+        let board = Board::from_str("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let mut evaluator = NnueEvaluator::new(model, &board);
+
+        let mv = Move::new(Square::A7, Square::A8, Some(Piece::Queen));
+        let new_board = board.make_move_new(mv);
+        evaluator.apply_move(&board, mv, &new_board);
+
+        assert_matches_scratch(&mut evaluator, model, &new_board);
+    }
+}
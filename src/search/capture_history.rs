@@ -0,0 +1,83 @@
+//! Capture-history heuristic for move ordering.
+//!
+//! Quiet moves get `HistoryTable`/`ContinuationHistory` to distinguish
+//! otherwise-equal-looking moves by track record. Captures had no
+//! equivalent: `score_move` orders them purely by MVV-LVA plus a one-shot
+//! SEE sign check, so two captures with identical victim/attacker types
+//! were always tied, and "losing" captures (negative SEE) were tied with
+//! each other too. `CaptureHistoryTable` fills that gap, indexed by
+//! `[attacker][to][captured]`, updated with the same `gravity` formula
+//! `HistoryTable` uses on beta cutoffs from capture moves.
+
+use crate::types::Piece;
+use super::gravity::{self, stat_bonus, stat_malus};
+
+/// Capture-history table: `[attacker][to][captured] -> score`.
+#[derive(Clone)]
+pub struct CaptureHistoryTable {
+    table: [[[i32; 6]; 64]; 6],
+}
+
+impl CaptureHistoryTable {
+    /// Create a new, empty capture-history table.
+    pub fn new() -> Self {
+        Self {
+            table: [[[0; 6]; 64]; 6],
+        }
+    }
+
+    /// Get the capture-history score for a capturing move.
+    #[inline]
+    pub fn get(&self, attacker: Piece, to: usize, captured: Piece) -> i32 {
+        self.table[attacker.index()][to][captured.index()]
+    }
+
+    /// Apply a signed, depth-scaled update with gravity, shared with
+    /// `HistoryTable::update` (see `gravity::apply`).
+    #[inline]
+    fn update(&mut self, attacker: Piece, to: usize, captured: Piece, delta: i32) {
+        gravity::apply(&mut self.table[attacker.index()][to][captured.index()], delta);
+    }
+
+    /// Beta cutoff on a capture move: reward `(attacker, to, captured)`
+    /// with `stat_bonus(depth)` and penalize every other capture already
+    /// searched at this node (which therefore failed to cause the cutoff)
+    /// with `stat_malus(depth)`, mirroring `HistoryTable::update_on_cutoff`.
+    pub fn update_on_cutoff(
+        &mut self,
+        attacker: Piece,
+        to: usize,
+        captured: Piece,
+        depth: i32,
+        searched_captures: &[(Piece, usize, Piece)],
+    ) {
+        self.update(attacker, to, captured, stat_bonus(depth));
+        let malus = stat_malus(depth);
+        for &(s_attacker, s_to, s_captured) in searched_captures {
+            let is_cutoff_move = s_attacker.index() == attacker.index()
+                && s_to == to
+                && s_captured.index() == captured.index();
+            if !is_cutoff_move {
+                self.update(s_attacker, s_to, s_captured, -malus);
+            }
+        }
+    }
+
+    /// Decay all scores toward zero. Called once per search (not per
+    /// node), same as `HistoryTable::age`.
+    pub fn age(&mut self) {
+        for attacker_table in &mut self.table {
+            for to_table in attacker_table {
+                for score in to_table {
+                    *score /= 2;
+                }
+            }
+        }
+    }
+}
+
+impl Default for CaptureHistoryTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,193 @@
+//! Continuation-history heuristic for move ordering.
+//!
+//! Generalizes a single-slot counter-move table: instead of
+//! remembering only the one move that last refuted a given opponent move,
+//! tracks a `[piece][to] -> [piece][to] -> score` table for each of a few
+//! plies back (the immediate predecessor, the position before that, and
+//! the same side's previous two moves), so ordering can reward a move that
+//! *usually* follows a given predecessor well, not just the one move that
+//! happened to work last time.
+//!
+//! `ContinuationStack` mirrors `EvalStack`'s per-ply path tracking;
+//! `ContinuationHistory`'s gravity update shares `gravity`'s bonus/malus/
+//! update math with `HistoryTable`'s.
+
+use crate::types::{Piece, Ply, MAX_PLY};
+use super::gravity::{self, stat_bonus, stat_malus};
+
+/// Plies back (from the node whose moves are being ordered) that each get
+/// their own table: the immediate predecessor and the position before it,
+/// then the same side's previous two moves, skipping the opponent's reply
+/// in between — the classic strong-engine schedule.
+const OFFSETS: [i32; 4] = [1, 2, 4, 6];
+
+/// Per-ply stack of "what moved where to reach this position": `stack[ply]`
+/// is the `(piece, to)` of the move played to get from `ply - 1` to `ply`,
+/// or `None` at the root. Set once per node (alongside `EvalStack`), so
+/// `ContinuationHistory` can look back a fixed number of plies without
+/// threading extra state through the recursion.
+#[derive(Clone)]
+pub struct ContinuationStack {
+    table: Vec<Option<(Piece, usize)>>,
+}
+
+impl ContinuationStack {
+    /// Create a new stack sized for the maximum search ply, with every
+    /// slot starting empty.
+    pub fn new() -> Self {
+        Self {
+            table: vec![None; MAX_PLY as usize + 1],
+        }
+    }
+
+    /// Record the `(piece, to)` of the move that produced the position at
+    /// `ply`, or clear it to `None` at the root.
+    #[inline]
+    pub fn set(&mut self, ply: Ply, entry: Option<(Piece, usize)>) {
+        self.table[ply.as_index()] = entry;
+    }
+
+    /// The `(piece, to)` of the move played `offset` plies before `ply`
+    /// (`offset == 1` is the immediate predecessor), or `None` if that
+    /// would reach past the root or no move was recorded there.
+    #[inline]
+    fn at_offset(&self, ply: Ply, offset: i32) -> Option<(Piece, usize)> {
+        let idx = ply.raw() - offset + 1;
+        if idx < 0 {
+            return None;
+        }
+        self.table[idx as usize]
+    }
+
+    /// Clear all recorded moves (call at the start of a new search).
+    pub fn clear(&mut self) {
+        for slot in &mut self.table {
+            *slot = None;
+        }
+    }
+}
+
+impl Default for ContinuationStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Number of `(piece, square)` combinations indexing one side of the table
+/// (6 piece types, 64 squares).
+const KEYS_PER_TABLE: usize = 6 * 64;
+
+/// One offset's `[piece][to] -> [piece][to] -> score` table, flattened into
+/// a single `Vec` (like `TranspositionTable` did before clustering) rather
+/// than a nested array, since `6 * 64 * 6 * 64` `i32`s is too large to
+/// build as a stack temporary in `Searcher`, which is cloned per helper
+/// thread.
+#[derive(Clone)]
+struct ContinuationTable {
+    table: Vec<i32>,
+}
+
+impl ContinuationTable {
+    fn new() -> Self {
+        Self {
+            table: vec![0; KEYS_PER_TABLE * KEYS_PER_TABLE],
+        }
+    }
+
+    #[inline]
+    fn index(prev_piece: Piece, prev_to: usize, piece: Piece, to: usize) -> usize {
+        (prev_piece.index() * 64 + prev_to) * KEYS_PER_TABLE + piece.index() * 64 + to
+    }
+
+    #[inline]
+    fn get(&self, prev_piece: Piece, prev_to: usize, piece: Piece, to: usize) -> i32 {
+        self.table[Self::index(prev_piece, prev_to, piece, to)]
+    }
+
+    /// Apply a signed, depth-scaled update with gravity, shared with
+    /// `HistoryTable::update` (see `gravity::apply`).
+    #[inline]
+    fn update(&mut self, prev_piece: Piece, prev_to: usize, piece: Piece, to: usize, delta: i32) {
+        let idx = Self::index(prev_piece, prev_to, piece, to);
+        gravity::apply(&mut self.table[idx], delta);
+    }
+
+    fn age(&mut self) {
+        for score in &mut self.table {
+            *score /= 2;
+        }
+    }
+}
+
+/// Continuation-history subsystem: one `ContinuationTable` per entry in
+/// `OFFSETS`.
+#[derive(Clone)]
+pub struct ContinuationHistory {
+    tables: [ContinuationTable; OFFSETS.len()],
+}
+
+impl ContinuationHistory {
+    pub fn new() -> Self {
+        Self {
+            tables: std::array::from_fn(|_| ContinuationTable::new()),
+        }
+    }
+
+    /// Sum of `(piece, to)`'s continuation-history score across every
+    /// offset in `OFFSETS` that's still on the board (i.e. doesn't reach
+    /// past the root), for use in move ordering in place of the old bare
+    /// counter-move equality check.
+    pub fn score(&self, path: &ContinuationStack, ply: Ply, piece: Piece, to: usize) -> i32 {
+        OFFSETS
+            .iter()
+            .zip(&self.tables)
+            .filter_map(|(&offset, table)| {
+                let (prev_piece, prev_to) = path.at_offset(ply, offset)?;
+                Some(table.get(prev_piece, prev_to, piece, to))
+            })
+            .sum()
+    }
+
+    /// Beta cutoff on a quiet move: reward `(piece, to)` with
+    /// `stat_bonus(depth)` and penalize every other quiet already searched
+    /// at this node with `stat_malus(depth)`, across every active offset —
+    /// mirroring `HistoryTable::update_on_cutoff`.
+    pub fn update_on_cutoff(
+        &mut self,
+        path: &ContinuationStack,
+        ply: Ply,
+        piece: Piece,
+        to: usize,
+        depth: i32,
+        searched_quiets: &[(Piece, usize)],
+    ) {
+        let bonus = stat_bonus(depth);
+        let malus = stat_malus(depth);
+        for (&offset, table) in OFFSETS.iter().zip(&mut self.tables) {
+            let Some((prev_piece, prev_to)) = path.at_offset(ply, offset) else {
+                continue;
+            };
+            table.update(prev_piece, prev_to, piece, to, bonus);
+            for &(q_piece, q_to) in searched_quiets {
+                if q_piece.index() == piece.index() && q_to == to {
+                    continue;
+                }
+                table.update(prev_piece, prev_to, q_piece, q_to, -malus);
+            }
+        }
+    }
+
+    /// Decay all scores toward zero (call once per search, like
+    /// `HistoryTable::age`).
+    pub fn age(&mut self) {
+        for table in &mut self.tables {
+            table.age();
+        }
+    }
+}
+
+impl Default for ContinuationHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,66 @@
+//! Per-ply static-eval stack for the "improving" heuristic.
+//!
+//! Tracks the static evaluation computed at each ply of the current search
+//! path so a node can tell whether the side to move's position has gotten
+//! better since its own last turn (two plies ago), without having to
+//! re-evaluate or thread extra state through the recursion.
+
+use crate::types::{Ply, Score, MAX_PLY};
+
+/// Static-eval table indexed by ply.
+#[derive(Clone)]
+pub struct EvalStack {
+    table: Vec<Option<Score>>,
+}
+
+impl EvalStack {
+    /// Create a new eval stack sized for the maximum search ply, with every
+    /// slot starting empty.
+    pub fn new() -> Self {
+        Self {
+            table: vec![None; MAX_PLY as usize + 1],
+        }
+    }
+
+    /// Get the static eval recorded at a given ply, if any was computed.
+    #[inline]
+    pub fn get(&self, ply: Ply) -> Option<Score> {
+        self.table[ply.as_index()]
+    }
+
+    /// Record the static eval for a given ply (or clear it to `None` when
+    /// this node has no valid eval, e.g. in check), overwriting whatever an
+    /// earlier visit at this ply left behind.
+    #[inline]
+    pub fn set(&mut self, ply: Ply, eval: Option<Score>) {
+        self.table[ply.as_index()] = eval;
+    }
+
+    /// Whether the side to move is "improving": its static eval at `ply` is
+    /// higher than it was two plies ago (its own last move). Defaults to
+    /// `false` when there's no eval two plies back (too shallow, or that
+    /// node was in check) or at `ply`.
+    #[inline]
+    pub fn improving(&self, ply: Ply, static_eval: Option<Score>) -> bool {
+        if ply.raw() < 2 {
+            return false;
+        }
+        match (static_eval, self.get(Ply::new(ply.raw() - 2))) {
+            (Some(now), Some(then)) => now > then,
+            _ => false,
+        }
+    }
+
+    /// Clear all recorded evals (call at the start of a new search).
+    pub fn clear(&mut self) {
+        for slot in &mut self.table {
+            *slot = None;
+        }
+    }
+}
+
+impl Default for EvalStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
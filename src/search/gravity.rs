@@ -0,0 +1,46 @@
+//! Shared "gravity" stat-update math for the history-style move-ordering
+//! heuristics (`HistoryTable`, `ContinuationHistory`, `CaptureHistoryTable`):
+//! a quadratic bonus for the move that caused a beta cutoff, a gentler
+//! linear malus for moves that were tried and failed to, and an update rule
+//! that pulls every entry toward `+/- MAX_HISTORY` instead of letting it
+//! grow without bound across a long search.
+
+/// Scores are kept within `+/- MAX_HISTORY` via `apply` below, so they
+/// saturate instead of overflowing across a long search.
+pub(crate) const MAX_HISTORY: i32 = 16384;
+
+/// Cap on a single `stat_bonus` call, well below `MAX_HISTORY` so a string
+/// of cutoffs still approaches the ceiling gradually via `apply`'s gravity
+/// rather than in one jump.
+pub(crate) const MAX_BONUS: i32 = 1800;
+
+/// Cap on a single `stat_malus` call. Lower than `MAX_BONUS`: failing to
+/// cut once is weaker evidence against a move than causing a cutoff is for
+/// it, so the gentler curve also tops out lower.
+pub(crate) const MAX_MALUS: i32 = 1200;
+
+/// Quadratic bonus for the move that caused the beta cutoff: rewards good
+/// moves increasingly steeply as depth grows.
+pub(crate) fn stat_bonus(depth: i32) -> i32 {
+    const A: i32 = 16;
+    const B: i32 = 32;
+    const C: i32 = 16;
+    (A * depth * depth + B * depth - C).min(MAX_BONUS)
+}
+
+/// Linear malus for moves searched before the cutoff move: a gentler curve
+/// than the bonus, since "didn't cut here" is weaker evidence than "did cut
+/// here".
+pub(crate) fn stat_malus(depth: i32) -> i32 {
+    const P: i32 = 24;
+    const Q: i32 = 16;
+    (P * depth - Q).min(MAX_MALUS)
+}
+
+/// Apply a signed, depth-scaled update to `*entry` with gravity toward
+/// `+/- MAX_HISTORY`, so repeated bonuses/maluses taper off instead of
+/// accumulating without bound.
+#[inline]
+pub(crate) fn apply(entry: &mut i32, delta: i32) {
+    *entry += delta - *entry * delta.abs() / MAX_HISTORY;
+}
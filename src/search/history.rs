@@ -0,0 +1,83 @@
+//! History heuristic for move ordering.
+//!
+//! Tracks a running score per (color, from, to) quiet move: positive when
+//! the move has caused beta cutoffs, negative when it was tried but failed
+//! to. Move ordering and history pruning both lean on this instead of
+//! re-discovering the same "this move usually doesn't work" per node.
+//!
+//! # Bonus/malus shaping
+//! On a cutoff the move that caused it gets a quadratic `stat_bonus(depth)`
+//! reward, while every other quiet already searched at this node (which
+//! therefore failed to cut) takes a gentler linear `stat_malus(depth)`
+//! penalty — good moves are rewarded increasingly steeply as depth grows,
+//! while merely-tried quiets aren't punished as hard, since "didn't cut
+//! here" is weaker evidence than "did cut here". See `gravity` for the
+//! shared bonus/malus/update math (also used by `ContinuationHistory` and
+//! `CaptureHistoryTable`).
+
+use crate::types::{Color, Move};
+use super::gravity::{self, stat_bonus, stat_malus};
+
+/// History heuristic table: `[color][from][to] -> score`.
+#[derive(Clone)]
+pub struct HistoryTable {
+    table: [[[i32; 64]; 64]; 2],
+}
+
+impl HistoryTable {
+    /// Create a new, empty history table.
+    pub fn new() -> Self {
+        Self {
+            table: [[[0; 64]; 64]; 2],
+        }
+    }
+
+    /// Get the history score for a quiet move.
+    #[inline]
+    pub fn get(&self, color: Color, m: Move) -> i32 {
+        let from = m.get_source().to_index();
+        let to = m.get_dest().to_index();
+        self.table[color.to_index()][from][to]
+    }
+
+    /// Apply a signed, depth-scaled update with gravity toward `+/-
+    /// MAX_HISTORY`, so repeated bonuses/maluses taper off instead of
+    /// accumulating without bound (see `gravity::apply`).
+    fn update(&mut self, color: Color, m: Move, delta: i32) {
+        let from = m.get_source().to_index();
+        let to = m.get_dest().to_index();
+        gravity::apply(&mut self.table[color.to_index()][from][to], delta);
+    }
+
+    /// Beta cutoff on a quiet move: reward `m` with `stat_bonus(depth)` and
+    /// penalize every other quiet already searched at this node (which
+    /// therefore failed to cause the cutoff) with `stat_malus(depth)`.
+    pub fn update_on_cutoff(&mut self, color: Color, m: Move, depth: i32, searched_quiets: &[Move]) {
+        self.update(color, m, stat_bonus(depth));
+        let malus = stat_malus(depth);
+        for &quiet in searched_quiets {
+            if quiet != m {
+                self.update(color, quiet, -malus);
+            }
+        }
+    }
+
+    /// Decay all scores toward zero. Called once per search (not per node)
+    /// so history carries over across iterative-deepening iterations while
+    /// still fading out stale data.
+    pub fn age(&mut self) {
+        for color_table in &mut self.table {
+            for from_table in color_table {
+                for score in from_table {
+                    *score /= 2;
+                }
+            }
+        }
+    }
+}
+
+impl Default for HistoryTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
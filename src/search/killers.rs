@@ -0,0 +1,53 @@
+//! Killer move heuristic for move ordering.
+//!
+//! Tracks up to two quiet moves per ply that caused a beta cutoff, so they
+//! can be tried early the next time the same ply is reached (e.g. in a
+//! sibling branch of the search tree).
+
+use crate::types::{Move, Ply, MAX_PLY};
+
+/// Killer move table indexed by ply, two slots per ply.
+#[derive(Clone)]
+pub struct KillerTable {
+    table: Vec<[Option<Move>; 2]>,
+}
+
+impl KillerTable {
+    /// Create a new, empty killer table sized for the maximum search ply.
+    pub fn new() -> Self {
+        Self {
+            table: vec![[None; 2]; MAX_PLY as usize + 1],
+        }
+    }
+
+    /// Get the killer moves for a given ply.
+    #[inline]
+    pub fn get(&self, ply: Ply) -> [Option<Move>; 2] {
+        self.table[ply.as_index()]
+    }
+
+    /// Store a new killer move for a ply, shifting the previous first killer
+    /// into the second slot. Duplicate stores are no-ops.
+    #[inline]
+    pub fn store(&mut self, ply: Ply, m: Move) {
+        let slot = &mut self.table[ply.as_index()];
+        if slot[0] == Some(m) {
+            return;
+        }
+        slot[1] = slot[0];
+        slot[0] = Some(m);
+    }
+
+    /// Clear all killer moves (call at the start of a new search).
+    pub fn clear(&mut self) {
+        for slot in &mut self.table {
+            *slot = [None, None];
+        }
+    }
+}
+
+impl Default for KillerTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -7,9 +7,9 @@
 //! - Infinite search (until stop)
 //! - Soft/hard time limits for optimal iteration control
 
-use crate::types::{Depth, Color};
+use crate::types::{Depth, Color, Move};
 use crate::uci::SearchParams;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// Search limits configuration
 #[derive(Debug, Clone, Default)]
@@ -32,6 +32,11 @@ pub struct SearchLimits {
     pub movestogo: Option<u32>,
     /// Infinite search
     pub infinite: bool,
+    /// Restrict the root to only these moves (UCI `searchmoves`); empty means
+    /// no restriction.
+    pub searchmoves: Vec<Move>,
+    /// Stop as soon as a mate in this many moves is proven (UCI `go mate N`).
+    pub mate: Option<u32>,
     /// Move overhead (safety buffer for network/GUI delay)
     pub move_overhead: u64,
 }
@@ -49,7 +54,7 @@ impl SearchLimits {
 
     pub fn depth(depth: i32) -> Self {
         Self {
-            depth: Some(Depth::new(depth)),
+            depth: Some(Depth::from_plies(depth)),
             move_overhead: Self::DEFAULT_MOVE_OVERHEAD,
             ..Default::default()
         }
@@ -66,6 +71,8 @@ impl SearchLimits {
             binc: params.binc,
             movestogo: params.movestogo,
             infinite: params.infinite,
+            searchmoves: params.searchmoves.clone(),
+            mate: params.mate,
             move_overhead: Self::DEFAULT_MOVE_OVERHEAD,
         }
     }
@@ -179,6 +186,17 @@ impl TimeManager {
         self.start_time = Some(Instant::now());
     }
 
+    /// Backdate the timer's start by `already_elapsed_ms`, grafting it onto
+    /// time already spent elsewhere (e.g. a ponder search's clock, reclaimed
+    /// at `ponderhit`) so `elapsed()`/`should_stop()` account for that time
+    /// too instead of this move getting its full budget on top of it.
+    pub fn seeded(mut self, already_elapsed_ms: u64) -> Self {
+        if let Some(start) = self.start_time {
+            self.start_time = Some(start - Duration::from_millis(already_elapsed_ms));
+        }
+        self
+    }
+
     /// Get elapsed time in milliseconds
     pub fn elapsed(&self) -> u64 {
         self.start_time
@@ -204,6 +222,19 @@ impl TimeManager {
         self.elapsed() < self.soft_limit
     }
 
+    /// Like `can_start_iteration`, but scales the soft limit by `scale`
+    /// first (capped at the hard limit either way). `scale < 1.0` lets a
+    /// confidently stable search stop before using its whole budget;
+    /// `scale > 1.0` extends it for an unsettled one (see
+    /// `Searcher::soft_limit_scale`).
+    pub fn can_start_iteration_scaled(&self, scale: f64) -> bool {
+        if self.infinite {
+            return true;
+        }
+        let scaled_soft = ((self.soft_limit as f64) * scale) as u64;
+        self.elapsed() < scaled_soft.min(self.hard_limit)
+    }
+
     /// Check if we've exceeded soft limit (use between iterations)
     pub fn soft_limit_exceeded(&self) -> bool {
         if self.infinite {
@@ -309,4 +340,34 @@ mod tests {
         assert!(tm.can_start_iteration());
         assert!(!tm.should_stop());
     }
+
+    #[test]
+    fn test_seeded_backdates_elapsed() {
+        let limits = SearchLimits {
+            movetime: Some(1000),
+            move_overhead: 50,
+            ..Default::default()
+        };
+        let tm = TimeManager::from_limits(&limits, Color::White).seeded(500);
+
+        // Already "spent" 500ms before this clock even started ticking, so
+        // elapsed() should immediately read close to that, not ~0.
+        assert!(tm.elapsed() >= 500);
+    }
+
+    #[test]
+    fn test_can_start_iteration_scaled() {
+        let limits = SearchLimits {
+            movetime: Some(1000),
+            move_overhead: 50,
+            ..Default::default()
+        };
+        let tm = TimeManager::from_limits(&limits, Color::White);
+
+        // Freshly started: elapsed is ~0, so any positive scale allows it.
+        assert!(tm.can_start_iteration_scaled(0.5));
+        // A huge scale also allows it: the scaled limit is capped at the
+        // hard limit, which hasn't passed yet either.
+        assert!(tm.can_start_iteration_scaled(100.0));
+    }
 }
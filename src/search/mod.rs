@@ -20,22 +20,33 @@ pub mod tt;
 mod killers;
 mod history;
 mod see;
-mod countermove;
+mod continuation;
+mod capture_history;
+mod gravity;
+mod eval_stack;
+mod reductions;
 pub mod node_types;
+mod skill;
 
 pub use node_types::{NodeType, Root, OnPV, OffPV};
 
 pub use limits::{SearchLimits, TimeManager};
 pub use negamax::SearchResult;
-pub use tt::TranspositionTable;
+pub(crate) use negamax::is_insufficient_material;
+pub use tt::{TranspositionTable, PreFetchable};
 pub use killers::KillerTable;
 pub use history::HistoryTable;
-pub use countermove::CounterMoveTable;
+pub use continuation::{ContinuationHistory, ContinuationStack};
+pub use capture_history::CaptureHistoryTable;
+pub use eval_stack::EvalStack;
+pub use reductions::Reductions;
 pub use see::{see, see_ge, is_good_capture};
+pub use skill::Skill;
 
 use crate::types::{Board, Move, Score, Depth, Ply, NodeCount};
 use crate::eval::{nnue, SearchEvaluator};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use crate::book::Rng;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::thread;
 
@@ -49,6 +60,9 @@ pub struct SearchStats {
     pub hashfull: u32,
     pub qnodes: NodeCount,
     pub eval_calls: u64,
+    /// Rolling TT-hit-rate average (0..=TT_HIT_AVERAGE_RESOLUTION), a proxy
+    /// for how familiar the current subtree is. See `Searcher::tt_hit_average`.
+    pub tt_hit_average: i64,
     // Profiling stats (ns)
     pub time_gen: u64,
     pub time_eval: u64,
@@ -77,27 +91,88 @@ impl SearchStats {
             println!("profiling: gen {}% eval {}% order {}% other {}%", 
                 gen_pct, eval_pct, order_pct, other_pct);
             
-             println!("stats: qnodes {} evals {}", self.qnodes, self.eval_calls);
+             println!("stats: qnodes {} evals {} tthits {}/{}", self.qnodes, self.eval_calls,
+                self.tt_hit_average, TT_HIT_AVERAGE_RESOLUTION);
         }
     }
 }
 
+/// Window (in TT probes) for the rolling hit-rate average in
+/// `Searcher::tt_hit_average`.
+pub const TT_HIT_AVERAGE_WINDOW: i64 = 4096;
+/// Fixed-point resolution for `Searcher::tt_hit_average`: a value of
+/// `TT_HIT_AVERAGE_RESOLUTION` means "every recent probe hit".
+pub const TT_HIT_AVERAGE_RESOLUTION: i64 = 1024;
+
+/// Default depth (in plies from the leaf) within which in-search tablebase
+/// probes are attempted; see `SharedState::tb_probe_depth`.
+pub const DEFAULT_TB_PROBE_DEPTH: i32 = 4;
+
+/// Fraction of the main TT's size given to `SharedState::qtt`. Quiescence
+/// positions are shallower and more numerous than main-search positions, so
+/// a smaller table is plenty, and keeping it separate means qsearch traffic
+/// never evicts full-depth entries out of `tt`.
+const QTT_SIZE_FRACTION: usize = 8;
+
+/// Decay applied to `Searcher::best_move_changes` every iteration, so only
+/// recent instability (not the whole game's history) affects timing.
+const BEST_MOVE_CHANGE_DECAY: f64 = 0.7;
+/// Centipawn drop versus the previous iteration's score that counts as
+/// "failing low at root" for `Searcher::score_fell`.
+const SCORE_FALL_THRESHOLD: i32 = 40;
+/// Soft-limit scale (see `Searcher::soft_limit_scale`) with zero recent
+/// instability and no score fall: below 1.0 so a long stable run stops
+/// before using its whole budget, the same intent the old stable-move-count
+/// cutoff had.
+const BASE_SOFT_LIMIT_SCALE: f64 = 0.6;
+/// Soft-limit scale added per unit of decayed `best_move_changes`.
+const INSTABILITY_SOFT_LIMIT_SCALE: f64 = 0.45;
+/// Extra soft-limit scale added while `score_fell` is set.
+const SCORE_FALL_SOFT_LIMIT_SCALE: f64 = 0.5;
+/// Extra soft-limit scale added per unit of "uncertainty" in
+/// `best_move_node_fraction` (i.e. `1.0 - fraction`): a best move that only
+/// absorbed a sliver of this iteration's nodes hasn't been searched with
+/// much conviction, so widen the limit the same way instability does.
+const NODE_FRACTION_SOFT_LIMIT_SCALE: f64 = 0.4;
+/// Bounds on the soft-limit scale factor.
+const MIN_SOFT_LIMIT_SCALE: f64 = 0.5;
+const MAX_SOFT_LIMIT_SCALE: f64 = 2.5;
+
 /// Shared state between search threads
 pub struct SharedState {
     /// Lock-free transposition table
     pub tt: TranspositionTable,
+    /// Separate, smaller transposition table for quiescence search, so the
+    /// heavy traffic of qsearch nodes (which repeat often via transpositions)
+    /// doesn't evict full-depth entries from `tt`.
+    pub qtt: TranspositionTable,
     /// Global stop flag
     pub stop: AtomicBool,
     /// Total nodes searched (sum across all threads)
     pub total_nodes: AtomicU64,
+    /// Loaded Syzygy tablebases (see `crate::tb`), shared read-only across
+    /// threads behind a lock only held for the `load_dir` swap.
+    pub tablebases: std::sync::RwLock<crate::tb::Tablebases>,
+    /// Only probe tablebases within this many plies of a leaf (probing
+    /// every node wastes time on subtrees deep enough that search alone
+    /// will resolve them just as well).
+    pub tb_probe_depth: AtomicI32,
+    /// Whether in-search TB scores respect the fifty-move rule (collapsing
+    /// cursed wins/blessed losses to plain draws) or report the raw WDL
+    /// result. UCI `Syzygy50MoveRule`.
+    pub tb_use_rule50: AtomicBool,
 }
 
 impl SharedState {
     pub fn new(hash_size_mb: usize) -> Self {
         Self {
             tt: TranspositionTable::new(hash_size_mb),
+            qtt: TranspositionTable::new((hash_size_mb / QTT_SIZE_FRACTION).max(1)),
             stop: AtomicBool::new(false),
             total_nodes: AtomicU64::new(0),
+            tablebases: std::sync::RwLock::new(crate::tb::Tablebases::default()),
+            tb_probe_depth: AtomicI32::new(DEFAULT_TB_PROBE_DEPTH),
+            tb_use_rule50: AtomicBool::new(true),
         }
     }
 }
@@ -108,6 +183,30 @@ impl Default for SharedState {
     }
 }
 
+impl SharedState {
+    /// (Re)index the tablebase directory at `dir`, replacing whatever was
+    /// loaded before. See `crate::tb` for what's actually probeable today.
+    pub fn load_tablebases(&self, dir: &str) -> std::io::Result<usize> {
+        let loaded = crate::tb::Tablebases::load_dir(std::path::Path::new(dir))?;
+        let count = loaded.signature_count();
+        *self.tablebases.write().unwrap() = loaded;
+        Ok(count)
+    }
+}
+
+/// Convert a tablebase WDL verdict to a search score, using the dedicated
+/// TB win/loss band (see `Score::tb_win_in`/`tb_loss_in`) so it compares
+/// correctly against real mate scores instead of being mistaken for one or
+/// clipped by a plain cp value.
+fn tb_wdl_to_score(wdl: crate::tb::Wdl, ply: i32) -> Score {
+    use crate::tb::Wdl;
+    match wdl {
+        Wdl::Win => Score::tb_win_in(ply),
+        Wdl::Loss => Score::tb_loss_in(ply),
+        Wdl::CursedWin | Wdl::BlessedLoss | Wdl::Draw => Score::draw(),
+    }
+}
+
 /// Main search controller
 pub struct Searcher {
     /// Current board position
@@ -118,8 +217,26 @@ pub struct Searcher {
     pub killers: KillerTable,
     /// History heuristic table (per-thread)
     pub history: HistoryTable,
-    /// Counter-move table (per-thread)
-    pub countermoves: CounterMoveTable,
+    /// Continuation-history tables (per-thread), generalizing the old
+    /// single-slot counter-move table across several plies back.
+    pub continuation_history: ContinuationHistory,
+    /// Per-ply stack of "what piece moved where" along the current search
+    /// path, feeding `continuation_history`'s lookback.
+    pub continuation_stack: ContinuationStack,
+    /// Capture-history table (per-thread), distinguishing otherwise-tied
+    /// captures (same attacker/victim types) by track record.
+    pub capture_history: CaptureHistoryTable,
+    /// Per-ply static-eval stack for the "improving" heuristic (is the side
+    /// to move's position better than it was two plies ago?).
+    pub eval_stack: EvalStack,
+    /// Precomputed LMR reduction table, rebuilt whenever the thread count
+    /// changes (see `set_threads`).
+    pub reductions: Reductions,
+    /// Rolling TT-hit-rate average, in `0..=TT_HIT_AVERAGE_RESOLUTION`,
+    /// updated after every TT probe (see `update_tt_hit_average`). A low
+    /// average is a proxy for an unexplored, tactically sharp subtree, so
+    /// LMR reduces one ply less while it's low.
+    pub tt_hit_average: i64,
     /// Time manager for search limits
     time_manager: TimeManager,
     /// Search statistics
@@ -132,16 +249,81 @@ pub struct Searcher {
     pub nnue: Option<nnue::Model>,
     /// Position history for repetition detection (stores Zobrist hashes)
     pub position_history: Vec<u64>,
-    /// Move stability counter (how many iterations best move unchanged)
-    stable_move_count: u32,
-    /// Last iteration's best move for stability tracking
+    /// Fifty-move-rule counter parallel to `position_history`: the halfmove
+    /// clock value after the move that produced the hash at the same index.
+    /// Reset to 0 on captures and pawn moves, incremented otherwise.
+    halfmove_clocks: Vec<u8>,
+    /// Contempt factor in centipawns: a small bonus/penalty applied to draw
+    /// scores so the engine avoids draws when it expects to be winning and
+    /// seeks them when it expects to be losing. Set via the UCI `Contempt`
+    /// option.
+    pub contempt: i32,
+    /// Whether draw scores get a tiny +/-1cp jitter (see `draw_score`) so
+    /// the engine doesn't shuffle into a repetition when several lines
+    /// look equally drawish. Disable for reproducible/deterministic
+    /// analysis runs.
+    pub draw_jitter: bool,
+    /// Exponentially-decayed count of how often the root best move has
+    /// changed across recent iterations (see `soft_limit_scale`): decayed by
+    /// `BEST_MOVE_CHANGE_DECAY` every iteration, bumped by 1 on a change.
+    /// High values mean an unsettled search and widen the soft time limit;
+    /// low values (a long stable run) narrow it so the engine stops early.
+    best_move_changes: f64,
+    /// Last iteration's best move, to detect a change for `best_move_changes`.
     last_best_move: Option<Move>,
+    /// Whether the most recently completed iteration's score fell by more
+    /// than `SCORE_FALL_THRESHOLD` versus the one before it ("failing low
+    /// at root"). Also widens the soft time limit via `soft_limit_scale`.
+    score_fell: bool,
+    /// Fraction of the most recently completed iteration's root nodes that
+    /// went into searching whichever move ended up `best_move` (see the
+    /// root-node bookkeeping in `negamax::search_impl`). A low fraction
+    /// means the best move barely stood out from its alternatives, so
+    /// `soft_limit_scale` widens the time budget the same way instability
+    /// does; defaults to 1.0 (fully confident) before any iteration completes.
+    best_move_node_fraction: f64,
+    /// Root node count at the start of the current root move loop, used to
+    /// compute `best_move_node_fraction` once the loop finishes.
+    root_nodes_before_move: u64,
+    /// Accumulated node count for whichever root move currently holds
+    /// `best_score`, refreshed every time a new move takes the lead.
+    root_best_move_nodes: u64,
     /// Number of threads to use for search
     num_threads: usize,
     /// Is this a helper thread (no UCI output)
     is_helper: bool,
+    /// Lazy SMP worker index (0 = main thread). Used to diversify helper
+    /// threads' aspiration windows so they don't all retread the exact
+    /// same search, while still sharing the TT.
+    thread_id: usize,
+    /// Restrict the root move list to these moves (UCI `searchmoves`);
+    /// empty means search every legal root move.
+    root_search_moves: Vec<Move>,
+    /// Stop iterative deepening as soon as a mate in this many moves is
+    /// proven (UCI `go mate N`).
+    mate_limit: Option<u32>,
+    /// Number of root lines to report (UCI `MultiPV` option). `1` is normal
+    /// single-line play; values above that make `search_internal` hunt for
+    /// the top-N root moves instead of just the best one.
+    pub multi_pv: usize,
+    /// Root moves already claimed by an earlier MultiPV line this depth;
+    /// excluded from the root move list so the next line finds a different
+    /// move. Cleared at the start of each depth's MultiPV pass.
+    excluded_root_moves: Vec<Move>,
+    /// Each MultiPV line's latest completed result, index 0 being the best
+    /// line. Persists across depths so a line that can't complete at a
+    /// deeper iteration (time cutoff) keeps reporting its last good result.
+    pv_lines: Vec<SearchResult>,
+    /// `UCI_Elo`/`UCI_LimitStrength` target, if strength limiting is on.
+    /// Caps `search`'s depth and, via `pick_move`, may replace `best_move`
+    /// with a weaker alternative. `None` means full strength.
+    skill: Option<Skill>,
 }
 
+/// How many root lines `search` gathers so `Skill::pick_move` has real
+/// alternatives to blunder into when the UCI `MultiPV` option itself is 1.
+const SKILL_CANDIDATE_LINES: usize = 4;
+
 impl Searcher {
     pub fn new() -> Self {
         Self {
@@ -149,17 +331,36 @@ impl Searcher {
             shared: Arc::new(SharedState::default()),
             killers: KillerTable::new(),
             history: HistoryTable::new(),
-            countermoves: CounterMoveTable::new(),
+            continuation_history: ContinuationHistory::new(),
+            continuation_stack: ContinuationStack::new(),
+            capture_history: CaptureHistoryTable::new(),
+            eval_stack: EvalStack::new(),
+            reductions: Reductions::new(1),
+            tt_hit_average: TT_HIT_AVERAGE_RESOLUTION,
             time_manager: TimeManager::new(),
             stats: SearchStats::default(),
             best_move: None,
             pv: Vec::new(),
             nnue: None,
             position_history: Vec::with_capacity(512),
-            stable_move_count: 0,
+            halfmove_clocks: Vec::with_capacity(512),
+            contempt: 10,
+            draw_jitter: true,
+            best_move_changes: 0.0,
             last_best_move: None,
+            score_fell: false,
+            best_move_node_fraction: 1.0,
+            root_nodes_before_move: 0,
+            root_best_move_nodes: 0,
             num_threads: 1,
             is_helper: false,
+            thread_id: 0,
+            root_search_moves: Vec::new(),
+            mate_limit: None,
+            multi_pv: 1,
+            excluded_root_moves: Vec::new(),
+            pv_lines: Vec::new(),
+            skill: None,
         }
     }
 
@@ -173,6 +374,13 @@ impl Searcher {
     /// Set number of search threads
     pub fn set_threads(&mut self, threads: usize) {
         self.num_threads = threads.max(1).min(64);
+        self.reductions = Reductions::new(self.num_threads);
+    }
+
+    /// Resize the transposition table (discards its contents, like other
+    /// engines do on a `Hash` change).
+    pub fn set_hash_size(&mut self, size_mb: usize) {
+        self.shared = Arc::new(SharedState::new(size_mb));
     }
     
     /// Get number of threads
@@ -185,25 +393,127 @@ impl Searcher {
         self.nnue = model;
     }
 
+    /// Set the contempt factor (centipawns) used to bias draw scores
+    pub fn set_contempt(&mut self, contempt: i32) {
+        self.contempt = contempt;
+    }
+
+    /// Enable or disable draw-score jitter (see `draw_score`). Disable for
+    /// reproducible/deterministic analysis runs.
+    pub fn set_draw_jitter(&mut self, enabled: bool) {
+        self.draw_jitter = enabled;
+    }
+
+    /// Set the number of root lines to report (UCI `MultiPV` option).
+    pub fn set_multi_pv(&mut self, lines: usize) {
+        self.multi_pv = lines.max(1);
+    }
+
+    /// Completed MultiPV lines from the last search, best line first.
+    pub fn pv_lines(&self) -> &[SearchResult] {
+        &self.pv_lines
+    }
+
+    /// Number of root lines `search` reports (UCI `MultiPV` option).
+    pub fn multi_pv(&self) -> usize {
+        self.multi_pv
+    }
+
+    /// Set (or clear, with `None`) the `UCI_Elo` strength target. See
+    /// `search`, which consults this to cap the depth and, via
+    /// `Skill::pick_move`, possibly replace the reported best move.
+    pub fn set_skill(&mut self, elo: Option<i32>) {
+        self.skill = elo.map(Skill::new);
+    }
+
+    /// (Re)index a Syzygy tablebase directory (UCI `SyzygyPath`). Returns
+    /// the number of material signatures found.
+    pub fn load_tablebases(&self, dir: &str) -> std::io::Result<usize> {
+        self.shared.load_tablebases(dir)
+    }
+
+    /// Set the in-search tablebase probe depth (UCI `SyzygyProbeDepth`):
+    /// how close to a leaf (in plies) a node must be before it's worth
+    /// probing.
+    pub fn set_tb_probe_depth(&mut self, depth: i32) {
+        self.shared.tb_probe_depth.store(depth, Ordering::Relaxed);
+    }
+
+    /// Toggle fifty-move-rule-aware tablebase scoring (UCI `Syzygy50MoveRule`).
+    pub fn set_tb_use_rule50(&mut self, enabled: bool) {
+        self.shared.tb_use_rule50.store(enabled, Ordering::Relaxed);
+    }
+
+    /// A draw-classified score (pure draw or contempt-biased), with a tiny
+    /// +/-1cp jitter derived from the node counter when `draw_jitter` is
+    /// enabled. Breaks ties between genuinely equal drawish lines (and
+    /// between a draw and a faintly better line) so the engine keeps
+    /// probing for winning chances instead of settling into a repetition.
+    pub fn draw_score(&self, base: Score) -> Score {
+        if !self.draw_jitter {
+            return base;
+        }
+        let jitter = if self.stats.nodes & 1 == 0 { 1 } else { -1 };
+        Score::cp(base.raw() + jitter)
+    }
+
     /// Set the position to search with history for repetition detection
     pub fn set_position(&mut self, board: Board) {
         self.position_history.clear();
         self.position_history.push(board.hash());
+        self.halfmove_clocks.clear();
+        self.halfmove_clocks.push(0);
         self.board = board;
     }
-    
-    /// Set position with move history for repetition detection
-    pub fn set_position_with_history(&mut self, board: Board, history: Vec<u64>) {
+
+    /// Set position with move history for repetition detection, and the
+    /// current fifty-move-rule halfmove clock (reversible plies played since
+    /// the last capture or pawn move).
+    pub fn set_position_with_history(&mut self, board: Board, history: Vec<u64>, halfmove_clock: u32) {
         self.position_history = history;
         self.position_history.push(board.hash());
+        self.halfmove_clocks.clear();
+        self.halfmove_clocks.push(halfmove_clock.min(u8::MAX as u32) as u8);
         self.board = board;
     }
-    
-    /// Check if position has repeated (for draw detection)
+
+    /// Check if position has repeated (for draw detection). Consults both
+    /// game history and the path searched so far, since both stacks share
+    /// `position_history`.
     pub fn is_repetition(&self, hash: u64) -> bool {
         self.position_history.iter().filter(|&&h| h == hash).count() >= 1
     }
 
+    /// Current fifty-move-rule halfmove clock at the node being searched.
+    pub fn halfmove_clock(&self) -> u32 {
+        *self.halfmove_clocks.last().unwrap_or(&0) as u32
+    }
+
+    /// Fold a TT probe's hit/miss outcome into the rolling `tt_hit_average`:
+    /// an exponential moving average over the last `TT_HIT_AVERAGE_WINDOW`
+    /// probes, in units of `TT_HIT_AVERAGE_RESOLUTION`.
+    #[inline]
+    pub fn update_tt_hit_average(&mut self, hit: bool) {
+        let sample = if hit { TT_HIT_AVERAGE_RESOLUTION } else { 0 };
+        self.tt_hit_average = (self.tt_hit_average * (TT_HIT_AVERAGE_WINDOW - 1) + sample)
+            / TT_HIT_AVERAGE_WINDOW;
+    }
+
+    /// Push a move played in-tree onto the history stacks, so descendants
+    /// can detect repetitions and the fifty-move rule along this search
+    /// path. `resets_clock` is true for captures and pawn moves.
+    pub fn push_move(&mut self, hash: u64, resets_clock: bool) {
+        self.position_history.push(hash);
+        let clock = if resets_clock { 0 } else { self.halfmove_clock().saturating_add(1).min(u8::MAX as u32) };
+        self.halfmove_clocks.push(clock as u8);
+    }
+
+    /// Undo the most recent `push_move`.
+    pub fn pop_move(&mut self) {
+        self.position_history.pop();
+        self.halfmove_clocks.pop();
+    }
+
     /// Get current statistics
     pub fn stats(&self) -> &SearchStats {
         &self.stats
@@ -242,226 +552,543 @@ impl Searcher {
         false
     }
     
-    /// Check if we can start a new iteration (soft time limit)
+    /// Check if we can start a new iteration (soft time limit, scaled by
+    /// recent instability — see `soft_limit_scale`).
     fn can_start_new_iteration(&self) -> bool {
         if self.shared.stop.load(Ordering::Relaxed) {
             return false;
         }
-        
-        // Check soft limit
-        if !self.time_manager.can_start_iteration() {
-            return false;
-        }
-        
-        // Early termination: if best move has been stable for many iterations
-        // and we've used a good portion of soft limit, we can stop early
-        // Be conservative - only stop if very confident
-        if self.stable_move_count >= 6 {
-            let elapsed = self.time_manager.elapsed();
-            let soft = self.time_manager.soft_limit_ms();
-            // Only stop early if we've used at least 40% of our soft limit
-            if elapsed > (soft * 2) / 5 {
-                return false;
-            }
+        self.time_manager.can_start_iteration_scaled(self.soft_limit_scale())
+    }
+
+    /// Scale factor for the soft time limit, replacing a binary
+    /// stable-move-count cutoff with continuous effort scaling: a long run
+    /// of unchanged best moves with no score drop narrows the budget so the
+    /// engine stops early, while a root that keeps changing its mind or
+    /// just failed low widens it so the next iteration gets a real chance
+    /// to settle.
+    fn soft_limit_scale(&self) -> f64 {
+        let mut scale = BASE_SOFT_LIMIT_SCALE + INSTABILITY_SOFT_LIMIT_SCALE * self.best_move_changes;
+        if self.score_fell {
+            scale += SCORE_FALL_SOFT_LIMIT_SCALE;
         }
-        
-        true
+        scale += NODE_FRACTION_SOFT_LIMIT_SCALE * (1.0 - self.best_move_node_fraction).max(0.0);
+        scale.clamp(MIN_SOFT_LIMIT_SCALE, MAX_SOFT_LIMIT_SCALE)
     }
     
-    /// Create a helper searcher that shares TT but has own tables
-    fn create_helper(&self) -> Self {
+    /// Create a helper searcher that shares TT but has own tables.
+    /// `thread_id` (1.. for helpers) diversifies its aspiration windows.
+    fn create_helper(&self, thread_id: usize) -> Self {
         Self {
             board: self.board.clone(),
             shared: Arc::clone(&self.shared),
             killers: KillerTable::new(),
             history: HistoryTable::new(),
-            countermoves: CounterMoveTable::new(),
+            continuation_history: ContinuationHistory::new(),
+            continuation_stack: ContinuationStack::new(),
+            capture_history: CaptureHistoryTable::new(),
+            eval_stack: EvalStack::new(),
+            reductions: self.reductions.clone(),
+            tt_hit_average: TT_HIT_AVERAGE_RESOLUTION,
             time_manager: self.time_manager.clone(),
             stats: SearchStats::default(),
             best_move: None,
             pv: Vec::new(),
             nnue: self.nnue.clone(),
             position_history: self.position_history.clone(),
-            stable_move_count: 0,
+            halfmove_clocks: self.halfmove_clocks.clone(),
+            contempt: self.contempt,
+            draw_jitter: self.draw_jitter,
+            best_move_changes: 0.0,
             last_best_move: None,
+            score_fell: false,
+            best_move_node_fraction: 1.0,
+            root_nodes_before_move: 0,
+            root_best_move_nodes: 0,
             num_threads: 1,
             is_helper: true,
+            thread_id,
+            root_search_moves: self.root_search_moves.clone(),
+            mate_limit: self.mate_limit,
+            multi_pv: self.multi_pv,
+            excluded_root_moves: Vec::new(),
+            pv_lines: Vec::new(),
+            skill: self.skill,
         }
     }
 
     /// Run the search with given limits (with Lazy SMP multi-threading)
     pub fn search(&mut self, limits: SearchLimits) -> SearchResult {
+        self.search_seeded(limits, 0)
+    }
+
+    /// Like `search`, but backdates the time manager's clock by
+    /// `already_elapsed_ms` first (see `TimeManager::seeded`). Used by
+    /// `cmd_ponderhit` to graft the real, timed search onto the clock a
+    /// ponder search already ran on, so the post-ponderhit search doesn't
+    /// get this move's full time budget on top of however long it already
+    /// pondered.
+    pub fn search_seeded(&mut self, limits: SearchLimits, already_elapsed_ms: u64) -> SearchResult {
         // Reset state
         self.shared.stop.store(false, Ordering::Relaxed);
         self.shared.total_nodes.store(0, Ordering::Relaxed);
         self.stats = SearchStats::default();
         self.best_move = None;
         self.pv.clear();
-        self.stable_move_count = 0;
+        self.best_move_changes = 0.0;
         self.last_best_move = None;
-        
+        self.score_fell = false;
+        self.best_move_node_fraction = 1.0;
+        self.pv_lines.clear();
+        self.excluded_root_moves.clear();
+
         // Increment TT generation for new search
         self.shared.tt.new_search();
+        self.shared.qtt.new_search();
         
         // Clear killer moves for new search
         self.killers.clear();
-        
+
+        // Clear the improving-heuristic eval stack for new search
+        self.eval_stack.clear();
+
+        // Clear the continuation-history path stack for new search (the
+        // tables themselves age below, same as history)
+        self.continuation_stack.clear();
+
+        // Reset the rolling TT-hit average for new search
+        self.tt_hit_average = TT_HIT_AVERAGE_RESOLUTION;
+
         // Age history scores (decay old data, keep some history)
         self.history.age();
-        
+        self.continuation_history.age();
+        self.capture_history.age();
+
         // Configure time management
-        self.time_manager = TimeManager::from_limits(&limits, self.board.turn());
-        
-        let max_depth = limits.depth.unwrap_or(Depth::MAX);
-        
-        // Spawn helper threads for Lazy SMP
+        self.time_manager = TimeManager::from_limits(&limits, self.board.turn()).seeded(already_elapsed_ms);
+
+        self.root_search_moves = limits.searchmoves.clone();
+        self.mate_limit = limits.mate;
+
+        // `go mate N` only needs to prove mate within 2N-1 plies; searching
+        // deeper than that can't shorten an already-found mate or find one
+        // that wasn't there, so cap the iterative deepening at 2N plies,
+        // one past that.
+        let mut max_depth = match (limits.depth, self.mate_limit) {
+            (Some(d), Some(n)) => Depth::from_plies(d.to_plies().min(2 * n as i32)),
+            (Some(d), None) => d,
+            (None, Some(n)) => Depth::from_plies(2 * n as i32),
+            (None, None) => Depth::MAX,
+        };
+
+        // Strength limiting: cap the depth, and if the UCI MultiPV option
+        // itself wasn't already asking for several lines, gather a few
+        // anyway so `Skill::pick_move` below has real alternatives to
+        // blunder into.
+        let skill = self.skill;
+        let restore_multi_pv = skill.map(|skill| {
+            max_depth = max_depth.min(skill.depth_cap());
+            let previous = self.multi_pv;
+            self.multi_pv = self.multi_pv.max(SKILL_CANDIDATE_LINES);
+            previous
+        });
+
+        let mut result = self.search_parallel(limits, max_depth);
+
+        if let Some(skill) = self.skill {
+            let candidates: Vec<(Move, Score)> = self.pv_lines
+                .iter()
+                .filter_map(|line| line.best_move.map(|m| (m, line.score)))
+                .collect();
+            let mut rng = Rng::from_entropy();
+            if let Some(chosen) = skill.pick_move(&candidates, &mut rng) {
+                if Some(chosen) != result.best_move {
+                    result.pv = vec![chosen];
+                    self.pv = result.pv.clone();
+                }
+                result.best_move = Some(chosen);
+                self.best_move = Some(chosen);
+            }
+        }
+        if let Some(previous) = restore_multi_pv {
+            self.multi_pv = previous;
+        }
+
+        result
+    }
+
+    /// Lazy SMP driver: spawns `num_threads - 1` helper threads that all
+    /// probe/store the same (lockless) transposition table as the main
+    /// thread, staggering which depths each one searches via the classic
+    /// skip-block scheme (see `should_skip_depth`) so they spend their time
+    /// exploring different parts of the tree instead of retreading the
+    /// main thread's work. Every thread shares the TT plus the atomic
+    /// stop/node-count flags in `self.shared`.
+    fn search_parallel(&mut self, limits: SearchLimits, max_depth: Depth) -> SearchResult {
         let mut handles = Vec::new();
-        
+
         if self.num_threads > 1 {
-            for _ in 1..self.num_threads {
-                let mut helper = self.create_helper();
+            for thread_id in 1..self.num_threads {
+                let mut helper = self.create_helper(thread_id);
                 let limits_clone = limits.clone();
                 let max_d = max_depth;
-                
-                let handle = thread::spawn(move || {
-                    helper.search_internal(limits_clone, max_d);
-                });
+
+                let handle = thread::spawn(move || helper.search_internal(limits_clone, max_d));
                 handles.push(handle);
             }
         }
-        
+
         // Main thread search (prints UCI output)
-        let result = self.search_internal(limits, max_depth);
-        
+        let mut result = self.search_internal(limits, max_depth);
+
         // Signal all helpers to stop
         self.shared.stop.store(true, Ordering::Relaxed);
-        
-        // Wait for all helper threads
+
+        // Collect helper results and keep the one from whichever thread got
+        // furthest: deepest completed iteration wins, ties broken by
+        // `Score::prefer` (shortest mate / longest getting-mated, else
+        // ordinary magnitude) so a thread that skipped to a deeper depth but
+        // found a worse line doesn't displace a shallower, better-scored
+        // result, and two mate-in-N finds at the same depth correctly pick
+        // the shorter mate rather than whichever happened to be scanned last.
+        let mut winner_is_helper = false;
         for handle in handles {
-            let _ = handle.join();
+            if let Ok(helper_result) = handle.join() {
+                let better = helper_result.stats.depth > result.stats.depth
+                    || (helper_result.stats.depth == result.stats.depth
+                        && helper_result.score.cmp_prefer(result.score) == std::cmp::Ordering::Greater);
+                if better {
+                    result = helper_result;
+                    winner_is_helper = true;
+                }
+            }
         }
-        
+
         // Get total nodes from all threads
-        self.stats.nodes = self.shared.total_nodes.load(Ordering::Relaxed);
-        
+        result.stats.nodes = self.shared.total_nodes.load(Ordering::Relaxed);
+        self.stats = result.stats.clone();
+        self.best_move = result.best_move;
+        self.pv = result.pv.clone();
+
+        // A helper's final iteration never got printed (only the main
+        // thread reports `info` lines as it searches), so if a helper's
+        // result won the vote, re-emit its line now to match what the GUI
+        // will see as `bestmove`.
+        if winner_is_helper {
+            let pv_str: String = result.pv.iter()
+                .map(|m| m.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            println!(
+                "info depth {} seldepth {} multipv 1 score {} nodes {} qnodes {} evals {} nps {} time {} hashfull {} pv {}",
+                result.stats.depth.to_plies(),
+                result.stats.seldepth.raw(),
+                result.score.to_uci(Score::neg_infinity(), Score::infinity()),
+                result.stats.nodes,
+                result.stats.qnodes,
+                result.stats.eval_calls,
+                result.stats.nps(),
+                result.stats.time_ms,
+                result.stats.hashfull,
+                pv_str
+            );
+        }
+
         result
     }
-    
+
+    /// Classic Lazy SMP "skip-block" depth staggering: whether helper thread
+    /// `self.thread_id` should skip searching this root `depth` so different
+    /// helpers bias toward different depths instead of all retreading the
+    /// main thread's iteration. The main thread (`thread_id == 0`) never
+    /// skips. Thread counts beyond the 20-entry tables wrap via modulo.
+    fn should_skip_depth(&self, depth: i32) -> bool {
+        const SKIP_SIZE: [i32; 20] = [1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 4, 4, 4, 4, 4, 4, 4, 4];
+        const SKIP_PHASE: [i32; 20] = [0, 1, 0, 1, 2, 3, 0, 1, 2, 3, 4, 5, 0, 1, 2, 3, 4, 5, 6, 7];
+
+        if self.thread_id == 0 {
+            return false;
+        }
+        let i = (self.thread_id - 1) % 20;
+        ((depth + SKIP_PHASE[i]) / SKIP_SIZE[i]) % 2 != 0
+    }
+
+    /// Probe tablebases at the root: when the position's material is
+    /// covered by a loaded table, rank/filter `root_search_moves` by DTZ so
+    /// the upcoming search only explores moves that hold the best proven
+    /// result, and, if the root result is itself fully proven (a WDL value
+    /// plus a complete DTZ ranking of every legal move), set `best_move`/
+    /// `pv` and return its score so the caller can skip searching entirely.
+    /// Returns `None` whenever no table covers this position — which,
+    /// since WDL/DTZ payload decoding isn't implemented yet (see
+    /// `crate::tb`), is every position today; the hook is live so decoding
+    /// drops straight in without touching call sites.
+    fn probe_root_tablebase(&mut self) -> Option<Score> {
+        let tb = self.shared.tablebases.read().unwrap();
+        if tb.is_empty() || self.board.combined().popcnt() > tb.max_pieces() {
+            return None;
+        }
+        let root_wdl = tb.probe_wdl(&self.board)?;
+
+        let mut moves: Vec<Move> = self.board.generate_moves().iter().collect();
+        tb.rank_root_moves(&self.board, &mut moves);
+        drop(tb);
+
+        self.root_search_moves = moves.clone();
+        let best = *moves.first()?;
+
+        let use_rule50 = self.shared.tb_use_rule50.load(Ordering::Relaxed);
+        let wdl = if use_rule50 { root_wdl } else { root_wdl.simple() };
+        self.best_move = Some(best);
+        self.pv = vec![best];
+        Some(tb_wdl_to_score(wdl, 0))
+    }
+
+    /// Run one root line's aspiration-window search at `depth`: start from
+    /// `prev_score +/- initial_window` (or the full `-inf..+inf` range when
+    /// there's no prior score or it's a mate), widening and re-searching on
+    /// fail-high/low until the score lands inside the window. Returns `None`
+    /// if the search was stopped before a result landed inside the window
+    /// (the caller should keep whatever it already had for this line).
+    /// Shared by single-PV and MultiPV search: each MultiPV line runs this
+    /// independently against its own root move list (see `excluded_root_moves`).
+    fn aspiration_search(
+        &mut self,
+        evaluator: &mut SearchEvaluator,
+        depth: i32,
+        initial_window: i32,
+        prev_score: Option<Score>,
+    ) -> Option<SearchResult> {
+        let mut delta = initial_window;
+        let can_narrow = depth > 1 && prev_score.map(|s| !s.is_mate()).unwrap_or(false);
+        let mut alpha = if can_narrow { prev_score.unwrap() - Score::cp(delta) } else { Score::neg_infinity() };
+        let mut beta = if can_narrow { prev_score.unwrap() + Score::cp(delta) } else { Score::infinity() };
+
+        loop {
+            let result = negamax::search::<Root>(
+                self,
+                evaluator,
+                &self.board.clone(),
+                Depth::from_plies(depth),
+                Ply::ZERO,
+                alpha,
+                beta,
+                None, // No prev move at root
+            );
+
+            if self.should_stop() {
+                return None;
+            }
+
+            if result.score <= alpha {
+                // Fail-low: report the bound before widening alpha, so a GUI
+                // watching `info` lines sees this iteration's score is only
+                // an upper bound, not the final value.
+                if !self.is_helper {
+                    println!(
+                        "info depth {} seldepth {} score {} nodes {} time {}",
+                        depth,
+                        result.stats.seldepth.raw(),
+                        result.score.to_uci(alpha, beta),
+                        self.shared.total_nodes.load(Ordering::Relaxed) + result.stats.nodes,
+                        self.time_manager.elapsed(),
+                    );
+                }
+                alpha = Score::neg_infinity();
+            } else if result.score >= beta {
+                // Fail-high: same, but this iteration's score is a lower bound.
+                if !self.is_helper {
+                    println!(
+                        "info depth {} seldepth {} score {} nodes {} time {}",
+                        depth,
+                        result.stats.seldepth.raw(),
+                        result.score.to_uci(alpha, beta),
+                        self.shared.total_nodes.load(Ordering::Relaxed) + result.stats.nodes,
+                        self.time_manager.elapsed(),
+                    );
+                }
+                beta = Score::infinity();
+            } else {
+                // Score within window, accept result
+                return Some(result);
+            }
+
+            // Widen window for next attempt
+            delta *= 2;
+            if delta > 500 {
+                alpha = Score::neg_infinity();
+                beta = Score::infinity();
+            }
+        }
+    }
+
     /// Internal search loop (called by main and helper threads)
     fn search_internal(&mut self, _limits: SearchLimits, max_depth: Depth) -> SearchResult {
         let mut best_score = Score::neg_infinity();
         const INITIAL_WINDOW: i32 = 25;
-        
+        // Stagger helper threads' aspiration windows so they don't all
+        // re-explore the exact same fail-high/low sequence; they still
+        // benefit from each other's TT entries regardless.
+        let initial_window = INITIAL_WINDOW + (self.thread_id as i32 * 3);
+
         // Initialize evaluator at root
         let local_nnue = self.nnue.clone();
         let mut root_evaluator = SearchEvaluator::new(local_nnue.as_ref(), &self.board);
 
-        for depth in 1..=max_depth.raw() {
+        // Previous iteration's accepted root score, used to derive this
+        // iteration's optimism term (see `crate::eval::compute_optimism`).
+        // `None` until the first iteration completes.
+        let mut prev_score: Option<Score> = None;
+
+        // Root tablebase probe: rank/filter root moves, and report a fully
+        // proven result immediately instead of searching at all.
+        if let Some(score) = self.probe_root_tablebase() {
+            self.stats.depth = max_depth;
+            return SearchResult {
+                best_move: self.best_move,
+                score,
+                pv: self.pv.clone(),
+                stats: self.stats.clone(),
+            };
+        }
+
+        for depth in 1..=max_depth.to_plies() {
             // Check if we can start a new iteration
             if !self.can_start_new_iteration() {
                 break;
             }
-            
-            // Early termination: stop when forced mate is found (winning or losing)
-            // No point searching further if we've found a forced mate
-            if best_score.is_mate_score() && self.best_move.is_some() {
-                break;
-            }
 
-            // Aspiration window: use previous score +/- delta after depth 1
-            let mut delta = INITIAL_WINDOW;
-            let mut alpha = if depth > 1 && !best_score.is_mate() { 
-                best_score - Score::cp(delta) 
-            } else { 
-                Score::neg_infinity() 
-            };
-            let mut beta = if depth > 1 && !best_score.is_mate() { 
-                best_score + Score::cp(delta) 
-            } else { 
-                Score::infinity() 
-            };
-
-            // Aspiration loop: widen window on fail-high/low
-            loop {
-                let result = negamax::search::<Root>(
-                    self,
-                    &mut root_evaluator,
-                    &self.board.clone(),
-                    Depth::new(depth),
-                    Ply::ZERO,
-                    alpha,
-                    beta,
-                    None,  // No prev move at root
-                );
+            // Skip-block depth staggering: helper threads sit out some
+            // iterations so they bias toward different depths than the main
+            // thread and each other, rather than all retreading the same
+            // tree. The main thread always searches every depth.
+            if self.should_skip_depth(depth) {
+                continue;
+            }
 
-                if self.should_stop() {
+            // Early termination: stop when forced mate is found (winning or losing).
+            // Under `go mate N`, only a mate proven within N moves counts; a
+            // longer mate found so far doesn't satisfy the request, so keep
+            // deepening (bounded by `max_depth` above) in case a shorter one exists.
+            // Skipped in MultiPV mode: a mate on the best line doesn't mean
+            // the other requested lines are done yet.
+            if self.multi_pv <= 1 && best_score.is_mate_score() && self.best_move.is_some() {
+                let satisfies_mate_limit = match self.mate_limit {
+                    Some(n) => best_score.mate_distance()
+                        .map(|plies| (plies + 1) / 2 <= n as i32)
+                        .unwrap_or(false),
+                    None => true,
+                };
+                if satisfies_mate_limit {
                     break;
                 }
+            }
 
-                // Check if score is within window
-                if result.score <= alpha {
-                    // Fail-low: widen alpha
-                    alpha = Score::neg_infinity();
-                } else if result.score >= beta {
-                    // Fail-high: widen beta
-                    beta = Score::infinity();
-                } else {
-                    // Score within window, accept result
-                    if let Some(m) = result.best_move {
-                        self.best_move = Some(m);
-                        best_score = result.score;
-                        self.pv = result.pv.clone();
+            // Recompute root optimism from the prior iteration's score; it
+            // flows into every child evaluator cloned from `root_evaluator`
+            // during this iteration's search.
+            root_evaluator.set_optimism(crate::eval::compute_optimism(prev_score));
+
+            // MultiPV: search `multi_pv` root lines, excluding each earlier
+            // line's move from the next so every line finds a different
+            // root move. Ties back into the single-PV aspiration-window
+            // code via `aspiration_search`; `multi_pv == 1` just runs it once.
+            self.excluded_root_moves.clear();
+            // `best_move_node_fraction` is written by `negamax::search_impl`
+            // from whichever root call is running; only line 0 (the actual
+            // `best_move`, not a MultiPV alternate) should feed
+            // `soft_limit_scale`, so stash it once that call lands and
+            // restore it after the rest of the MultiPV lines have run.
+            let mut best_line_node_fraction = self.best_move_node_fraction;
+            for pv_index in 0..self.multi_pv {
+                let line_prev_score = self.pv_lines.get(pv_index).map(|r| r.score);
+                match self.aspiration_search(&mut root_evaluator, depth, initial_window, line_prev_score) {
+                    Some(result) => {
+                        if pv_index == 0 {
+                            best_line_node_fraction = self.best_move_node_fraction;
+                        }
+                        let no_more_moves = result.best_move.is_none();
+                        if let Some(m) = result.best_move {
+                            self.excluded_root_moves.push(m);
+                        }
+                        if pv_index < self.pv_lines.len() {
+                            self.pv_lines[pv_index] = result;
+                        } else {
+                            self.pv_lines.push(result);
+                        }
+                        if no_more_moves {
+                            // Fewer legal root moves than requested lines.
+                            break;
+                        }
                     }
-                    break;
+                    None => break, // Stopped mid-search; keep prior lines.
                 }
+            }
+            self.best_move_node_fraction = best_line_node_fraction;
+            self.excluded_root_moves.clear();
 
-                // Widen window for next attempt
-                delta *= 2;
-                if delta > 500 {
-                    alpha = Score::neg_infinity();
-                    beta = Score::infinity();
+            // The best line drives `best_move`/`pv`/`best_score` and the
+            // next iteration's aspiration window and optimism term.
+            let score_before_iteration = prev_score;
+            if let Some(best_line) = self.pv_lines.first() {
+                if let Some(m) = best_line.best_move {
+                    self.best_move = Some(m);
+                    best_score = best_line.score;
+                    self.pv = best_line.pv.clone();
+                    prev_score = Some(best_score);
                 }
             }
+            self.score_fell = matches!(
+                (score_before_iteration, prev_score),
+                (Some(old), Some(new)) if new.raw() < old.raw() - SCORE_FALL_THRESHOLD
+            );
 
-            self.stats.depth = Depth::new(depth);
+            self.stats.depth = Depth::from_plies(depth);
             self.stats.hashfull = self.shared.tt.hashfull();
-            
+            self.stats.tt_hit_average = self.tt_hit_average;
+
             // Update time from time manager
             self.stats.time_ms = self.time_manager.elapsed();
-            
+
             // Report nodes to shared counter
             self.shared.total_nodes.fetch_add(self.stats.nodes, Ordering::Relaxed);
-            
-            // Track move stability for early termination
-            if self.best_move == self.last_best_move {
-                self.stable_move_count += 1;
-            } else {
-                self.stable_move_count = 0;
+
+            // Track root-move instability for `soft_limit_scale`: decay the
+            // running count every iteration, then bump it on a change.
+            self.best_move_changes *= BEST_MOVE_CHANGE_DECAY;
+            if self.best_move != self.last_best_move {
+                self.best_move_changes += 1.0;
                 self.last_best_move = self.best_move;
             }
 
-            // Print info for this depth (main thread only)
+            // Print info for this depth (main thread only): one `multipv K`
+            // line per completed line, best first.
             if !self.is_helper && !self.should_stop() {
                 self.stats.print_profiling();
                 self.stats.time_search = (self.time_manager.elapsed() as u64) * 1_000_000;
-                let pv_str: String = self.pv.iter()
-                    .map(|m| m.to_string())
-                    .collect::<Vec<_>>()
-                    .join(" ");
-                    
-                println!(
-                    "info depth {} seldepth {} score {} nodes {} qnodes {} evals {} nps {} time {} hashfull {} pv {}",
-                    depth,
-                    self.stats.seldepth.raw(),
-                    best_score,
-                    self.shared.total_nodes.load(Ordering::Relaxed),
-                    self.stats.qnodes,
-                    self.stats.eval_calls,
-                    self.stats.nps(),
-                    self.stats.time_ms,
-                    self.stats.hashfull,
-                    pv_str
-                );
+                let total_nodes = self.shared.total_nodes.load(Ordering::Relaxed);
+
+                for (i, line) in self.pv_lines.iter().enumerate() {
+                    let pv_str: String = line.pv.iter()
+                        .map(|m| m.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ");
+
+                    println!(
+                        "info depth {} seldepth {} multipv {} score {} nodes {} qnodes {} evals {} nps {} time {} hashfull {} pv {}",
+                        depth,
+                        self.stats.seldepth.raw(),
+                        i + 1,
+                        line.score.to_uci(Score::neg_infinity(), Score::infinity()),
+                        total_nodes,
+                        self.stats.qnodes,
+                        self.stats.eval_calls,
+                        self.stats.nps(),
+                        self.stats.time_ms,
+                        self.stats.hashfull,
+                        pv_str
+                    );
+                }
             }
         }
 
@@ -526,3 +1153,90 @@ impl Default for Searcher {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_multi_pv_clamps_to_at_least_one() {
+        let mut searcher = Searcher::new();
+        searcher.set_multi_pv(0);
+        assert_eq!(searcher.multi_pv, 1);
+        searcher.set_multi_pv(4);
+        assert_eq!(searcher.multi_pv, 4);
+    }
+
+    #[test]
+    fn test_main_thread_never_skips_depth() {
+        let searcher = Searcher::new();
+        for depth in 1..64 {
+            assert!(!searcher.should_skip_depth(depth));
+        }
+    }
+
+    #[test]
+    fn test_helper_skip_pattern_matches_skip_block_formula() {
+        const SKIP_SIZE: [i32; 20] = [1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 4, 4, 4, 4, 4, 4, 4, 4];
+        const SKIP_PHASE: [i32; 20] = [0, 1, 0, 1, 2, 3, 0, 1, 2, 3, 4, 5, 0, 1, 2, 3, 4, 5, 6, 7];
+
+        let main = Searcher::new();
+        for thread_id in 1..=40usize {
+            let helper = main.create_helper(thread_id);
+            let i = (thread_id - 1) % 20;
+            for depth in 1..32 {
+                let expected = ((depth + SKIP_PHASE[i]) / SKIP_SIZE[i]) % 2 != 0;
+                assert_eq!(helper.should_skip_depth(depth), expected,
+                    "thread_id {thread_id} depth {depth}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_soft_limit_scale_narrows_when_stable_widens_when_unstable() {
+        let mut searcher = Searcher::new();
+        let stable_scale = searcher.soft_limit_scale();
+        assert!(stable_scale < 1.0, "a freshly-reset searcher has no recorded instability");
+
+        searcher.best_move_changes = 1.0;
+        let unstable_scale = searcher.soft_limit_scale();
+        assert!(unstable_scale > stable_scale);
+
+        searcher.score_fell = true;
+        let fell_scale = searcher.soft_limit_scale();
+        assert!(fell_scale > unstable_scale);
+        assert!(fell_scale <= MAX_SOFT_LIMIT_SCALE);
+    }
+
+    #[test]
+    fn test_soft_limit_scale_widens_when_best_move_node_fraction_low() {
+        let mut searcher = Searcher::new();
+        assert_eq!(searcher.best_move_node_fraction, 1.0,
+            "a freshly-reset searcher assumes full confidence until an iteration completes");
+        let confident_scale = searcher.soft_limit_scale();
+
+        searcher.best_move_node_fraction = 0.1;
+        let uncertain_scale = searcher.soft_limit_scale();
+        assert!(uncertain_scale > confident_scale);
+        assert!(uncertain_scale <= MAX_SOFT_LIMIT_SCALE);
+    }
+
+    #[test]
+    fn test_skip_block_team_covers_every_depth() {
+        // The whole point of staggering is that no depth falls through the
+        // cracks: for any team size, every depth must still be searched by
+        // at least one thread (the main thread, which never skips, already
+        // guarantees this alone, but helpers should be covering plenty of
+        // depths too rather than all skipping in lockstep).
+        let main = Searcher::new();
+        for num_threads in 1..=8usize {
+            let team: Vec<Searcher> = (0..num_threads).map(|id| main.create_helper(id)).collect();
+            for depth in 1..32 {
+                assert!(
+                    team.iter().any(|t| !t.should_skip_depth(depth)),
+                    "no thread in a team of {num_threads} searched depth {depth}"
+                );
+            }
+        }
+    }
+}
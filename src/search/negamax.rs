@@ -8,8 +8,9 @@
 //! Future extensions: null move pruning, LMR, futility pruning
 
 use super::{Searcher, SearchStats, ordering, qsearch, see};
-use super::tt::BoundType;
-use crate::types::{Board, Move, Score, Depth, Ply, Piece, SCORE_MATE};
+use super::tt::{BoundType, PreFetchable};
+use super::node_types::{NodeType, OffPV};
+use crate::types::{Board, Move, Score, Depth, Ply, Piece, SCORE_MATE, mate_distance_prune};
 use crate::eval::SearchEvaluator;
 use std::time::Instant;
 
@@ -22,8 +23,29 @@ pub struct SearchResult {
     pub stats: SearchStats,
 }
 
-/// Main negamax search function with TT integration and null move pruning
-pub fn search(
+/// Main negamax search function with TT integration and null move pruning.
+///
+/// `NT` selects the compile-time node type (`Root`, `OnPV`, `OffPV`), letting
+/// the compiler specialize PV-only bookkeeping (like the root's aspiration
+/// bookkeeping) without runtime branching. Null move is always allowed at
+/// entry; internal probes that must disable it (e.g. the null move search
+/// itself) recurse into `search_impl` directly.
+pub fn search<NT: NodeType>(
+    searcher: &mut Searcher,
+    evaluator: &mut SearchEvaluator,
+    board: &Board,
+    depth: Depth,
+    ply: Ply,
+    alpha: Score,
+    beta: Score,
+    prev_move: Option<Move>,
+) -> SearchResult {
+    search_impl::<NT>(searcher, evaluator, board, depth, ply, alpha, beta, true, prev_move)
+}
+
+/// Node-type-specialized search body, shared by `search` and all of its
+/// internal recursive probes (null move, ProbCut, IID, PVS re-searches).
+fn search_impl<NT: NodeType>(
     searcher: &mut Searcher,
     evaluator: &mut SearchEvaluator,
     board: &Board,
@@ -39,26 +61,41 @@ pub fn search(
 
     let hash = board.hash();
 
-    // === Repetition Detection with Contempt ===
-    // Check for draw by repetition (position seen before in game history)
-    // Use contempt: avoid draws when winning, seek draws when losing
-    if ply.raw() > 0 && searcher.is_repetition(hash) {
-        // Contempt factor: small penalty/bonus for draws based on expected score
-        // If alpha > 0 (we expect to be winning), penalize draws to avoid them
-        // If beta < 0 (we expect to be losing), reward draws to seek them
-        const CONTEMPT: i32 = 10; // Small contempt factor (centipawns)
-        
-        let draw_score = if alpha.raw() > CONTEMPT {
+    // Record what moved here for `continuation_history`'s lookback (see
+    // `ContinuationStack`); `None` at the root, since no move led there.
+    searcher.continuation_stack.set(
+        ply,
+        prev_move.and_then(|pm| board.piece_on(pm.get_dest()).map(|piece| (piece, pm.get_dest().to_index()))),
+    );
+
+    // === Draw Detection with Contempt ===
+    // Repetition (game history or the path searched so far), the fifty-move
+    // rule, and insufficient material all score as a draw, biased by the
+    // engine's contempt setting. Use contempt: avoid draws when winning,
+    // seek draws when losing.
+    // The parent's `push_move` already put this node's own key last in
+    // `position_history`, so only the entries before it count as an earlier
+    // occurrence (a null-move child gets no push; the parent key it skips
+    // can't match its own).
+    let earlier_history = &searcher.position_history[..searcher.position_history.len().saturating_sub(1)];
+    let is_draw = (ply.raw() > 0 && earlier_history.contains(&hash))
+        || searcher.halfmove_clock() >= 100
+        || is_insufficient_material(board);
+    if is_draw {
+        let contempt = searcher.contempt;
+
+        let draw_score = if alpha.raw() > contempt {
             // We're winning - penalize draws to avoid repetition
-            Score::cp(-CONTEMPT)
-        } else if beta.raw() < -CONTEMPT {
-            // We're losing - reward draws to seek repetition  
-            Score::cp(CONTEMPT)
+            Score::cp(-contempt)
+        } else if beta.raw() < -contempt {
+            // We're losing - reward draws to seek repetition
+            Score::cp(contempt)
         } else {
             // Close to equal - treat as pure draw
             Score::draw()
         };
-        
+        let draw_score = searcher.draw_score(draw_score);
+
         return SearchResult {
             best_move: None,
             score: draw_score,
@@ -67,39 +104,25 @@ pub fn search(
         };
     }
 
-    // Mate distance pruning
-    let mate_score = SCORE_MATE - ply.raw() as i32;
-    let mated_score = -SCORE_MATE + ply.raw() as i32;
-
-    if alpha.raw() < mated_score {
-        alpha = Score(mated_score);
-        if alpha >= beta {
-            return SearchResult {
-                best_move: None,
-                score: alpha,
-                pv: Vec::new(),
-                stats: searcher.stats().clone(),
-            };
-        }
-    }
-
-    if beta.raw() > mate_score {
-        beta = Score(mate_score);
-        if alpha >= beta {
-            return SearchResult {
-                best_move: None,
-                score: beta,
-                pv: Vec::new(),
-                stats: searcher.stats().clone(),
-            };
-        }
+    // Mate distance pruning: even mating on the very next move can't beat a
+    // shorter mate already found higher in the tree, and the symmetric bound
+    // applies for being mated (see `mate_distance_prune`).
+    if mate_distance_prune(&mut alpha, &mut beta, ply.raw()) {
+        return SearchResult {
+            best_move: None,
+            score: alpha,
+            pv: Vec::new(),
+            stats: searcher.stats().clone(),
+        };
     }
 
     let orig_alpha = alpha;
     let mut tt_move: Option<Move> = None;
 
     // === TT Probe ===
-    if let Some(entry) = searcher.shared.tt.probe(hash) {
+    let tt_entry = searcher.shared.tt.probe(hash);
+    searcher.update_tt_hit_average(tt_entry.is_some());
+    if let Some(entry) = tt_entry {
         tt_move = entry.best_move();
         
         // Only use TT score if depth is sufficient
@@ -116,7 +139,10 @@ pub fn search(
                     };
                 }
                 BoundType::LowerBound => {
-                    if tt_score >= beta {
+                    // PV nodes never take a non-exact cutoff: the TT bound alone
+                    // isn't proof of the true value, and a cutoff here would
+                    // leave the principal variation untrustworthy.
+                    if !NT::PV && tt_score >= beta {
                         return SearchResult {
                             best_move: tt_move,
                             score: tt_score,
@@ -129,7 +155,7 @@ pub fn search(
                     }
                 }
                 BoundType::UpperBound => {
-                    if tt_score <= alpha {
+                    if !NT::PV && tt_score <= alpha {
                         return SearchResult {
                             best_move: tt_move,
                             score: tt_score,
@@ -153,22 +179,71 @@ pub fn search(
         };
     }
 
+    // === Tablebase Probe ===
+    // Near leaves (within `tb_probe_depth` plies), a covered position's WDL
+    // result cuts the subtree outright rather than searching down to it.
+    // Skipped at the root (handled by `Searcher::probe_root_tablebase`
+    // instead, which also needs to pick a move) and whenever no table
+    // covers this position — always, today, since WDL payload decoding
+    // isn't implemented yet (see `crate::tb`).
+    if ply.raw() > 0 && depth.to_plies() <= searcher.shared.tb_probe_depth.load(std::sync::atomic::Ordering::Relaxed) {
+        let tb = searcher.shared.tablebases.read().unwrap();
+        if !tb.is_empty() && board.combined().popcnt() <= tb.max_pieces() {
+            if let Some(wdl) = tb.probe_wdl(board) {
+                let use_rule50 = searcher.shared.tb_use_rule50.load(std::sync::atomic::Ordering::Relaxed);
+                let wdl = if use_rule50 { wdl } else { wdl.simple() };
+                drop(tb);
+                return SearchResult {
+                    best_move: None,
+                    score: super::tb_wdl_to_score(wdl, ply.raw()),
+                    pv: Vec::new(),
+                    stats: searcher.stats().clone(),
+                };
+            }
+        }
+    }
+
     let in_check = board.in_check();
 
     // === Reverse Futility Pruning (RFP) ===
     // If we are way ahead, we can prune without searching
     // Distinct from standard Futility Pruning which prunes *moves*
     let mut static_eval = None;
-    if !in_check && depth.raw() <= 7 {
-        searcher.inc_eval_calls();
-        let t_eval = Instant::now();
-        let eval = evaluator.evaluate(board);
-        searcher.add_eval_time(t_eval.elapsed().as_nanos() as u64);
-        static_eval = Some(eval);
+    if !in_check && depth.to_plies() <= 7 {
+        // A TT hit already carries a cached static eval (see `TTEntry::static_eval`);
+        // reuse it instead of paying for another NNUE evaluation.
+        let cached_eval = tt_entry.map(|e| e.static_eval()).filter(|s| *s != Score::none());
+        if let Some(eval) = cached_eval {
+            static_eval = Some(eval);
+        } else {
+            searcher.inc_eval_calls();
+            let t_eval = Instant::now();
+            let eval = evaluator.evaluate(board);
+            searcher.add_eval_time(t_eval.elapsed().as_nanos() as u64);
+            static_eval = Some(eval);
+        }
+    } else {
+        // No static eval at this node (in check, or deeper than the RFP
+        // depth limit): clear this ply's
+        // slot so a stale eval from an earlier visit can't leak into a
+        // descendant's "improving" check two plies from now.
+        searcher.eval_stack.set(ply, None);
+    }
+
+    // === Improving Heuristic ===
+    // Is the side to move's position better than it was on its own last
+    // move (two plies ago)? Used below to scale RFP, futility and LMR:
+    // prune/reduce more aggressively when not improving, less when we are.
+    let improving = searcher.eval_stack.improving(ply, static_eval);
+    if static_eval.is_some() {
+        searcher.eval_stack.set(ply, static_eval);
+    }
+
+    if let Some(eval) = static_eval {
+        // RFP margin shrinks when improving: a position that's already
+        // trending up needs less of a cushion to trust the cutoff.
+        let margin = Score::cp(75 * (depth.to_plies() - improving as i32));
 
-        // RFP Margin: 75 * depth (tuneable)
-        let margin = Score::cp(75 * depth.raw() as i32);
-        
         if eval - margin >= beta {
              return SearchResult {
                 best_move: None,
@@ -181,11 +256,11 @@ pub fn search(
 
     // === ProbCut ===
     const PROBCUT_MARGIN: i32 = 100;
-    if depth.raw() >= 5 && (beta.raw() - alpha.raw() == 1) && !in_check && beta.raw().abs() < (SCORE_MATE - 1000) {
+    if depth.to_plies() >= 5 && (beta.raw() - alpha.raw() == 1) && !in_check && beta.raw().abs() < (SCORE_MATE - 1000) {
         let probe_beta = beta + Score::cp(PROBCUT_MARGIN);
-        let probe_depth = Depth::new(depth.raw() - 4);
+        let probe_depth = depth - 4;
 
-        let result = search(
+        let result = search_impl::<OffPV>(
             searcher,
             evaluator,
             board,
@@ -209,7 +284,7 @@ pub fn search(
 
     // === Null Move Pruning ===
     // Skip if: in check, depth too low, null move disabled, or only king+pawns
-    if allow_null && !in_check && depth.raw() >= 3 {
+    if allow_null && !in_check && depth.to_plies() >= 3 {
         // Don't do null move in pure pawn endgames (zugzwang risk)
         let dominated_by_pawns = (board.piece_bb(Piece::Knight)
             | board.piece_bb(Piece::Bishop)
@@ -218,19 +293,19 @@ pub fn search(
         
         if !dominated_by_pawns {
             // Reduction: R=5 if depth > 6, else R=4 (aggressive)
-            let r = if depth.raw() > 6 { 5 } else { 4 };
+            let r = if depth.to_plies() > 6 { 5 } else { 4 };
             
             // Create a null move board (pass the turn)
             let null_board = board.make_null_move();
             
             // Clone evaluator for null move (no piece updates needed)
             let mut null_evaluator = evaluator.clone();
-            
-            let null_result = search(
+
+            let null_result = search_impl::<OffPV>(
                 searcher,
                 &mut null_evaluator,
                 &null_board,
-                Depth::new((depth.raw() - 1 - r).max(0)),
+                (depth - (1 + r)).max(Depth::ZERO),
                 ply.next(),
                 -beta,
                 -beta + Score::cp(1),
@@ -254,10 +329,10 @@ pub fn search(
 
     // === Internal Iterative Deepening (IID) ===
     // If we are at a PV node and have no TT move, search shallower to find one
-    if tt_move.is_none() && depth.raw() >= 6 && (beta.raw() - alpha.raw() > 1) {
-        let iid_depth = Depth::new(depth.raw() - 2);
-        
-        let result = search(
+    if tt_move.is_none() && depth.to_plies() >= 6 && (beta.raw() - alpha.raw() > 1) {
+        let iid_depth = depth - 2;
+
+        let result = search_impl::<NT>(
             searcher,
             evaluator,
             board,
@@ -282,7 +357,7 @@ pub fn search(
         let score = if board.in_check() {
             Score::mated_in(ply.raw())
         } else {
-            Score::draw()
+            searcher.draw_score(Score::draw())
         };
         return SearchResult {
             best_move: None,
@@ -292,29 +367,52 @@ pub fn search(
         };
     }
 
-    // Quiescence search at depth 0
+    // Quiescence search at depth 0. `quiescence` mutates its board in place
+    // via make/unmake, so hand it a local copy rather than the `&Board` this
+    // node is searching (cheap: `Board` is `Copy`).
     if depth.is_qs() {
-        return qsearch::quiescence(searcher, evaluator, board, ply, alpha, beta);
+        let mut qboard = *board;
+        return qsearch::quiescence::<NT>(searcher, evaluator, &mut qboard, ply, alpha, beta);
     }
 
     // Get killers for this ply
     let killers = searcher.killers.get(ply);
     let color = board.turn();
-    
-    // Get counter-move for opponent's previous move
-    let counter_move = prev_move.and_then(|pm| searcher.countermoves.get(pm));
 
     // Collect moves into a Vec for ordering
     let mut move_vec: Vec<Move> = moves.iter().collect();
-    
-    // Order moves (TT, killers, counter-move, and history)
+
+    if NT::ROOT {
+        // UCI `searchmoves`: restrict the root to the requested subset.
+        if !searcher.root_search_moves.is_empty() {
+            move_vec.retain(|m| searcher.root_search_moves.contains(m));
+        }
+        // MultiPV: earlier lines this depth already claimed these moves, so
+        // exclude them and let this line find the next-best root move.
+        if !searcher.excluded_root_moves.is_empty() {
+            move_vec.retain(|m| !searcher.excluded_root_moves.contains(m));
+        }
+    }
+
+    // Order moves (TT, killers, history, and continuation history)
     let t_order = Instant::now();
-    ordering::order_moves_full(board, &mut move_vec, tt_move, killers, counter_move, &searcher.history, color);
+    ordering::order_moves_full(
+        board,
+        &mut move_vec,
+        tt_move,
+        killers,
+        &searcher.history,
+        &searcher.continuation_history,
+        &searcher.continuation_stack,
+        &searcher.capture_history,
+        ply,
+        color,
+    );
     searcher.add_order_time(t_order.elapsed().as_nanos() as u64);
 
     // Static eval is already computed for RFP if depth <= 7
     // If not (e.g. was in check check or deeper), compute it now if needed for Razoring/Futility
-    if static_eval.is_none() && depth.raw() <= 3 && !in_check {
+    if static_eval.is_none() && depth.to_plies() <= 3 && !in_check {
         searcher.inc_eval_calls();
         let t_eval = Instant::now();
         let val = evaluator.evaluate(board);
@@ -323,11 +421,12 @@ pub fn search(
     }
     
     // Razoring
-    if depth.raw() <= 3 && (beta.raw() - alpha.raw() == 1) && !in_check {
+    if depth.to_plies() <= 3 && (beta.raw() - alpha.raw() == 1) && !in_check {
         if let Some(eval) = static_eval {
-            let threshold = alpha - Score::cp(200 + depth.raw() as i32 * 60);
+            let threshold = alpha - Score::cp(200 + depth.to_plies() * 60);
             if eval < threshold {
-                let result = qsearch::quiescence(searcher, evaluator, board, ply, alpha, beta);
+                let mut qboard = *board;
+                let result = qsearch::quiescence::<NT>(searcher, evaluator, &mut qboard, ply, alpha, beta);
                  if result.score < alpha {
                     return result; 
                 }
@@ -341,8 +440,25 @@ pub fn search(
     // Use fixed-size array for searched quiets to avoid allocations
     let mut searched_quiets: [Move; 64] = [Move::NULL; 64];
     let mut quiets_count = 0usize;
+    // Same fixed-size tracking for captures, feeding `capture_history`'s
+    // cutoff update below.
+    let mut searched_captures: [Move; 64] = [Move::NULL; 64];
+    let mut captures_count = 0usize;
+
+    // Root-only node-fraction bookkeeping (see `Searcher::soft_limit_scale`):
+    // nodes spent before this call's own move loop, so the fraction below
+    // only covers nodes this loop actually spent rather than its ancestors'.
+    if NT::ROOT {
+        searcher.root_nodes_before_move = searcher.stats().nodes;
+        searcher.root_best_move_nodes = 0;
+    }
 
     for (move_idx, &m) in move_vec.iter().enumerate() {
+        // Root-only node-fraction bookkeeping (see `Searcher::soft_limit_scale`):
+        // remember how many nodes this move took so that, if it ends up
+        // `best_move`, the time manager can tell how decisively it won.
+        let move_nodes_before = if NT::ROOT { searcher.stats().nodes } else { 0 };
+
         let new_board = board.make_move_new(m);
 
         // Prefetch TT entry for next position
@@ -355,6 +471,23 @@ pub fn search(
         let is_quiet = !is_capture && !is_promotion;
         let gives_check = new_board.in_check();
 
+        // === Late Move Pruning (LMP) ===
+        // Once move_idx passes a depth/improving-scaled move-count
+        // threshold at shallow depth, skip the remaining quiet, non-check
+        // moves outright: deep-but-hopeless quiet tails aren't worth the
+        // time, as long as we're not already losing.
+        if depth.to_plies() <= 8 && !in_check && !best_score.is_mated() && is_quiet && !gives_check {
+            let depth_plies = depth.to_plies();
+            let futility_move_count = (5 + depth_plies * depth_plies) * (1 + improving as i32) / 2;
+            if move_idx as i32 >= futility_move_count {
+                if quiets_count < 64 {
+                    searched_quiets[quiets_count] = m;
+                    quiets_count += 1;
+                }
+                continue;
+            }
+        }
+
         // LMR: Late Move Reductions
         // Reduce depth for late quiet moves that aren't special
         let mut reduced = false;
@@ -362,29 +495,37 @@ pub fn search(
         // Check extension: extend +1 when in check to avoid horizon effect
         let extension = if in_check { 1 } else { 0 };
         
-        let search_depth = if move_idx >= 2 
-            && depth.raw() >= 3 
-            && is_quiet 
-            && !in_check 
+        let search_depth = if move_idx >= 2
+            && depth.to_plies() >= 3
+            && is_quiet
+            && !in_check
             && !gives_check
             && !is_killer
         {
-            // Logarithmic reduction formula
-            let d = (depth.raw() as f32).ln();
-            let m_idx = ((move_idx + 1) as f32).ln();
-            let reduction = ((d * m_idx) / 1.9) as i32;
-            let reduction = reduction.min(depth.raw() - 2).max(1);
+            // Table-lookup reduction (see `Reductions`), folding in PV-ness
+            // and the improving flag instead of recomputing `ln` per move.
+            let reduction = searcher.reductions.reduction(depth.to_plies(), move_idx, NT::PV, improving);
+            // A low recent TT-hit rate is a proxy for an unexplored,
+            // tactically sharp subtree: reduce one ply less there so we
+            // don't skip over something real.
+            const TT_HIT_AVERAGE_REDUCE_THRESHOLD: i64 = super::TT_HIT_AVERAGE_RESOLUTION * 427 / 1024;
+            let reduction = if searcher.tt_hit_average < TT_HIT_AVERAGE_REDUCE_THRESHOLD {
+                reduction - 1
+            } else {
+                reduction
+            };
+            let reduction = reduction.min(depth.to_plies() - 2).max(1);
             reduced = true;
-            Depth::new((depth.raw() - 1 - reduction + extension).max(1))
+            (depth - (1 + reduction - extension)).max(Depth::ONE)
         } else {
-            Depth::new((depth.raw() - 1 + extension).max(0))
+            (depth - (1 - extension)).max(Depth::ZERO)
         };
 
         // === History Pruning ===
         // Prune quiet moves that have historically failed significantly
-        if depth.raw() < 4 && is_quiet && !in_check && !gives_check && !is_killer && move_idx > 0 {
+        if depth.to_plies() < 4 && is_quiet && !in_check && !gives_check && !is_killer && move_idx > 0 {
             // Threshold: -3000 * depth (e.g. -3000 at d1, -6000 at d2)
-            let threshold = -3000 * depth.raw() as i32;
+            let threshold = -3000 * depth.to_plies();
             if searcher.history.get(color, m) < threshold {
                  // Track for history stats if needed, or just prune
                 continue;
@@ -393,7 +534,7 @@ pub fn search(
 
         // === SEE Pruning for Quiet Moves ===
         // Prune quiet moves that are obvious blunders (e.g. putting a piece en prise)
-        if depth.raw() <= 4 && is_quiet && !in_check && !gives_check && move_idx > 0 {
+        if depth.to_plies() <= 4 && is_quiet && !in_check && !gives_check && move_idx > 0 {
              // If move loses material (at least 50cp), prune it
              // This uses SEE to see if the move is "safe"
              if !see::see_ge(board, m, -50) {
@@ -402,10 +543,12 @@ pub fn search(
         }
 
         // === Futility Pruning ===
-        // At shallow depths, skip quiet moves if eval + margin is below alpha
+        // At shallow depths, skip quiet moves if eval + margin is below alpha.
+        // Tighten the margin when not improving: a position that isn't
+        // trending up is less likely to be rescued by a quiet move anyway.
         if let Some(se) = static_eval {
             if is_quiet && !gives_check && move_idx > 0 {
-                let margin = 150 * depth.raw();
+                let margin = 150 * (depth.to_plies() - !improving as i32);
                 if se.raw() + margin < alpha.raw() {
                     // Track for history
                     if quiets_count < 64 {
@@ -417,10 +560,15 @@ pub fn search(
             }
         }
 
+        // Track this move on the shared history stacks so descendants see
+        // repetitions and the fifty-move clock along this search path too.
+        let is_pawn_move = board.piece_on(m.get_source()) == Some(Piece::Pawn);
+        searcher.push_move(new_board.hash(), is_capture || is_pawn_move);
+
         // === Principal Variation Search (PVS) ===
         let mut result;
         let mut score;
-        
+
         if move_idx == 0 {
             // Incremental update for next depth
             let mut child_eval = evaluator.clone();
@@ -429,7 +577,7 @@ pub fn search(
             }
 
             // First move: search with full window
-            result = search(
+            result = search_impl::<NT::Next>(
                 searcher,
                 &mut child_eval,
                 &new_board,
@@ -449,7 +597,7 @@ pub fn search(
             }
 
             // Later moves: null window search first
-            result = search(
+            result = search_impl::<OffPV>(
                 searcher,
                 &mut child_eval,
                 &new_board,
@@ -461,11 +609,11 @@ pub fn search(
                 Some(m),
             );
             score = -result.score;
-            
+
             // Re-search with full window if fails high
             if score > alpha && score < beta && !searcher.should_stop() {
                 // Re-use same child_eval since board/move didn't change
-                result = search(
+                result = search_impl::<NT::Next>(
                     searcher,
                     &mut child_eval,
                     &new_board,
@@ -487,11 +635,11 @@ pub fn search(
                 child_eval.refresh(&new_board);
             }
 
-            result = search(
+            result = search_impl::<NT::Next>(
                 searcher,
                 &mut child_eval,
                 &new_board,
-                Depth::new((depth.raw() - 1 + extension).max(0)),
+                (depth - (1 - extension)).max(Depth::ZERO),
                 ply.next(),
                 -beta,
                 -alpha,
@@ -501,11 +649,16 @@ pub fn search(
             score = -result.score;
         }
 
+        searcher.pop_move();
+
         if searcher.should_stop() {
             break;
         }
 
         if score > best_score {
+            if NT::ROOT {
+                searcher.root_best_move_nodes = searcher.stats().nodes - move_nodes_before;
+            }
             best_score = score;
             best_move = Some(m);
 
@@ -517,25 +670,63 @@ pub fn search(
                 alpha = score;
 
                 if score >= beta {
-                    // Beta cutoff - update killer, history, and counter-move for quiet moves
+                    // Beta cutoff - update killer, history, and continuation history for quiet moves
                     if is_quiet {
                         searcher.killers.store(ply, m);
                         // Update history: bonus for cutoff move, penalty for searched quiets
-                        searcher.history.update_on_cutoff(color, m, depth.raw(), &searched_quiets[..quiets_count]);
-                        // Update counter-move
-                        if let Some(pm) = prev_move {
-                            searcher.countermoves.store(pm, m);
+                        searcher.history.update_on_cutoff(color, m, depth.to_plies(), &searched_quiets[..quiets_count]);
+                        // Update continuation history the same way, across every active
+                        // ply offset (see `ContinuationHistory`)
+                        if let Some(piece) = board.piece_on(m.get_source()) {
+                            let searched_quiet_pieces: Vec<(Piece, usize)> = searched_quiets[..quiets_count]
+                                .iter()
+                                .filter_map(|&q| board.piece_on(q.get_source()).map(|p| (p, q.get_dest().to_index())))
+                                .collect();
+                            searcher.continuation_history.update_on_cutoff(
+                                &searcher.continuation_stack,
+                                ply,
+                                piece,
+                                m.get_dest().to_index(),
+                                depth.to_plies(),
+                                &searched_quiet_pieces,
+                            );
+                        }
+                    } else if is_capture {
+                        // Same bonus/malus treatment for captures: reward the
+                        // cutoff capture, penalize other captures already
+                        // searched at this node (see `CaptureHistoryTable`).
+                        if let (Some(attacker), Some(captured)) =
+                            (board.piece_on(m.get_source()), board.piece_at(m.to()).map(|(p, _)| p))
+                        {
+                            let searched_capture_tuples: Vec<(Piece, usize, Piece)> = searched_captures[..captures_count]
+                                .iter()
+                                .filter_map(|&c| {
+                                    let a = board.piece_on(c.get_source())?;
+                                    let v = board.piece_at(c.to()).map(|(p, _)| p)?;
+                                    Some((a, c.get_dest().to_index(), v))
+                                })
+                                .collect();
+                            searcher.capture_history.update_on_cutoff(
+                                attacker,
+                                m.get_dest().to_index(),
+                                captured,
+                                depth.to_plies(),
+                                &searched_capture_tuples,
+                            );
                         }
                     }
                     break;
                 }
             }
         }
-        
+
         // Track searched quiet moves for history penalty
         if is_quiet && quiets_count < 64 {
             searched_quiets[quiets_count] = m;
             quiets_count += 1;
+        } else if is_capture && captures_count < 64 {
+            searched_captures[captures_count] = m;
+            captures_count += 1;
         }
     }
 
@@ -553,11 +744,21 @@ pub fn search(
             hash,
             best_move,
             best_score.to_tt(ply.raw()),
+            static_eval.unwrap_or(Score::none()),
             depth,
             bound,
         );
     }
 
+    if NT::ROOT {
+        let loop_nodes = searcher.stats().nodes - searcher.root_nodes_before_move;
+        searcher.best_move_node_fraction = if loop_nodes > 0 {
+            searcher.root_best_move_nodes as f64 / loop_nodes as f64
+        } else {
+            1.0
+        };
+    }
+
     SearchResult {
         best_move,
         score: best_score,
@@ -565,3 +766,21 @@ pub fn search(
         stats: searcher.stats().clone(),
     }
 }
+
+/// Whether the position has insufficient material for either side to force
+/// checkmate: no pawns, rooks, or queens on the board, and at most one minor
+/// piece (knight or bishop) total. Covers K vs K, K+N vs K, and K+B vs K.
+///
+/// `pub(crate)` so `crate::selfplay`'s standalone game driver can use the same
+/// draw check outside of search, not just `search_impl`'s in-search one.
+pub(crate) fn is_insufficient_material(board: &Board) -> bool {
+    let heavy_or_pawns = board.piece_bb(Piece::Pawn)
+        | board.piece_bb(Piece::Rook)
+        | board.piece_bb(Piece::Queen);
+    if !heavy_or_pawns.is_empty() {
+        return false;
+    }
+
+    let minors = board.piece_bb(Piece::Knight) | board.piece_bb(Piece::Bishop);
+    minors.popcnt() <= 1
+}
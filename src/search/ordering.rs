@@ -3,8 +3,10 @@
 //! Good move ordering is critical for alpha-beta pruning efficiency.
 //! Uses lazy selection sort to avoid full sort overhead.
 
-use crate::types::{Board, Move, Piece, Color, piece_value};
+use crate::types::{Board, Move, Piece, Color, Ply, piece_value};
 use super::history::HistoryTable;
+use super::continuation::{ContinuationHistory, ContinuationStack};
+use super::capture_history::CaptureHistoryTable;
 use super::see;
 
 /// Move score constants
@@ -13,7 +15,6 @@ const PROMOTION_BONUS: i32 = 100_000;
 const GOOD_CAPTURE_BONUS: i32 = 60_000;
 const KILLER_0_BONUS: i32 = 40_000;
 const KILLER_1_BONUS: i32 = 35_000;
-const COUNTER_MOVE_BONUS: i32 = 30_000;
 const BAD_CAPTURE_PENALTY: i32 = -10_000;
 
 /// MVV-LVA scores for capture ordering
@@ -33,12 +34,15 @@ fn mvv_lva_score(board: &Board, m: Move) -> i32 {
 /// Score a move for ordering (higher = search first)
 #[inline]
 pub fn score_move(
-    board: &Board, 
-    m: Move, 
+    board: &Board,
+    m: Move,
     tt_move: Option<Move>,
     killers: [Option<Move>; 2],
-    counter_move: Option<Move>,
     history: &HistoryTable,
+    continuation_history: &ContinuationHistory,
+    continuation_stack: &ContinuationStack,
+    capture_history: &CaptureHistoryTable,
+    ply: Ply,
     color: Color,
 ) -> i32 {
     // TT move is always searched first
@@ -56,29 +60,38 @@ pub fn score_move(
     // Captures: skip SEE for obviously good captures (victim >= attacker)
     if m.is_capture() {
         let mvv_lva = mvv_lva_score(board, m);
+        // Capture history: distinguishes otherwise-tied captures (same
+        // attacker/victim types) by track record, both among good
+        // captures and among each other's bad captures.
+        let capture_hist = match (board.piece_on(m.get_source()), board.piece_at(m.to()).map(|(p, _)| p)) {
+            (Some(attacker), Some(captured)) => capture_history.get(attacker, m.get_dest().to_index(), captured),
+            _ => 0,
+        };
         if mvv_lva >= 0 {
             // Winning or equal capture (e.g., PxQ, NxN) - skip expensive SEE
-            score += GOOD_CAPTURE_BONUS + mvv_lva;
+            score += GOOD_CAPTURE_BONUS + mvv_lva + capture_hist;
         } else {
             // Potentially losing capture - use SEE to verify
             let see_value = see::see(board, m);
             if see_value >= 0 {
-                score += GOOD_CAPTURE_BONUS + mvv_lva;
+                score += GOOD_CAPTURE_BONUS + mvv_lva + capture_hist;
             } else {
-                score += BAD_CAPTURE_PENALTY + mvv_lva;
+                score += BAD_CAPTURE_PENALTY + mvv_lva + capture_hist;
             }
         }
     } else {
-        // Quiet move - check killers and counter-move
+        // Quiet move - check killers, else rank by history plus
+        // continuation history (generalizes the old single-slot
+        // counter-move bonus into a summed signal over several plies back)
         if killers[0] == Some(m) {
             score += KILLER_0_BONUS;
         } else if killers[1] == Some(m) {
             score += KILLER_1_BONUS;
-        } else if counter_move == Some(m) {
-            score += COUNTER_MOVE_BONUS;
         } else {
-            // Use history score for other quiet moves
             score += history.get(color, m);
+            if let Some(piece) = board.piece_on(m.get_source()) {
+                score += continuation_history.score(continuation_stack, ply, piece, m.get_dest().to_index());
+            }
         }
     }
 
@@ -87,20 +100,34 @@ pub fn score_move(
 
 #[allow(dead_code)]
 pub fn order_moves_full(
-    board: &Board, 
-    moves: &mut [Move], 
+    board: &Board,
+    moves: &mut [Move],
     tt_move: Option<Move>,
     killers: [Option<Move>; 2],
-    counter_move: Option<Move>,
     history: &HistoryTable,
+    continuation_history: &ContinuationHistory,
+    continuation_stack: &ContinuationStack,
+    capture_history: &CaptureHistoryTable,
+    ply: Ply,
     color: Color,
 ) {
     // Score moves in place
     let mut scores: [i32; 256] = [0; 256];
     let count = moves.len().min(256);
-    
+
     for i in 0..count {
-        scores[i] = score_move(board, moves[i], tt_move, killers, counter_move, history, color);
+        scores[i] = score_move(
+            board,
+            moves[i],
+            tt_move,
+            killers,
+            history,
+            continuation_history,
+            continuation_stack,
+            capture_history,
+            ply,
+            color,
+        );
     }
     
     // Selection sort by scores (in-place, no allocation)
@@ -124,22 +151,40 @@ pub fn order_moves_full(
 
 #[allow(dead_code)]
 pub fn order_moves_with_tt_and_killers(
-    board: &Board, 
-    moves: &mut [Move], 
+    board: &Board,
+    moves: &mut [Move],
     tt_move: Option<Move>,
     killers: [Option<Move>; 2],
 ) {
     let dummy_history = HistoryTable::new();
-    order_moves_full(board, moves, tt_move, killers, None, &dummy_history, Color::White);
+    let dummy_continuation_history = ContinuationHistory::new();
+    let dummy_continuation_stack = ContinuationStack::new();
+    let dummy_capture_history = CaptureHistoryTable::new();
+    order_moves_full(
+        board,
+        moves,
+        tt_move,
+        killers,
+        &dummy_history,
+        &dummy_continuation_history,
+        &dummy_continuation_stack,
+        &dummy_capture_history,
+        Ply::ZERO,
+        Color::White,
+    );
 }
 
 #[allow(dead_code)]
-pub fn order_captures(board: &Board, moves: &mut [Move]) {
+pub fn order_captures(board: &Board, moves: &mut [Move], tt_move: Option<Move>) {
     let mut scores: [i32; 256] = [0; 256];
     let count = moves.len().min(256);
-    
+
     for i in 0..count {
-        scores[i] = mvv_lva_score(board, moves[i]);
+        scores[i] = if tt_move == Some(moves[i]) {
+            TT_MOVE_BONUS
+        } else {
+            mvv_lva_score(board, moves[i])
+        };
     }
     
     for i in 0..count {
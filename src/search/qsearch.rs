@@ -11,7 +11,8 @@ use super::{Searcher, ordering};
 use super::negamax::SearchResult;
 use super::node_types::NodeType;
 use super::see::is_good_capture;
-use crate::types::{Board, Move, Score, Ply, Piece};
+use super::tt::BoundType;
+use crate::types::{Board, Move, Score, Depth, Ply, Piece, MakeUnmake};
 use crate::eval::SearchEvaluator;
 use std::time::Instant;
 
@@ -38,13 +39,34 @@ fn piece_value(piece: Piece) -> i32 {
     PIECE_VALUES[piece.index()]
 }
 
+/// Classify a quiescence result against the window it was searched with, for
+/// `shared.qtt` storage: a fail-high is a lower bound, a fail-low against the
+/// window's original alpha is an upper bound, and anything else is exact.
+#[inline]
+fn qtt_bound(score: Score, orig_alpha: Score, beta: Score) -> BoundType {
+    if score >= beta {
+        BoundType::LowerBound
+    } else if score > orig_alpha {
+        BoundType::Exact
+    } else {
+        BoundType::UpperBound
+    }
+}
+
 /// Quiescence search - search captures only to avoid horizon effect.
 ///
 /// Uses compile-time node type specialization via the `NodeType` trait.
+/// Mutates `board`/`evaluator` in place via make/unmake (see `MakeUnmake`,
+/// `SearchEvaluator::apply_move`) instead of cloning a fresh board and
+/// evaluator per capture, undoing each move immediately after its recursive
+/// call returns. Probes `shared.qtt` (a separate, smaller table from the
+/// main search's TT) before doing any of that work, and stores back into it
+/// on the way out so repeated visits to the same quiescence node can skip
+/// straight to a cached score.
 pub fn quiescence<NT: NodeType>(
     searcher: &mut Searcher,
     evaluator: &mut SearchEvaluator,
-    board: &Board,
+    board: &mut Board,
     ply: Ply,
     mut alpha: Score,
     beta: Score,
@@ -53,6 +75,52 @@ pub fn quiescence<NT: NodeType>(
     searcher.inc_qnodes();
     searcher.update_seldepth(ply);
 
+    let orig_alpha = alpha;
+    let hash = board.hash();
+
+    // === QTT Probe ===
+    // Quiescence nodes transpose heavily, so a probe here can skip the
+    // stand-pat eval and capture search entirely. Uses `shared.qtt` (not the
+    // main `tt`) so qsearch traffic can't evict full-depth entries.
+    let qtt_entry = searcher.shared.qtt.probe(hash);
+    let mut tt_move = None;
+    if let Some(entry) = qtt_entry {
+        tt_move = entry.best_move();
+        let tt_score = entry.score().from_tt(ply.raw());
+
+        match entry.bound() {
+            BoundType::Exact => {
+                return SearchResult {
+                    best_move: None,
+                    score: tt_score,
+                    pv: Vec::new(),
+                    stats: searcher.stats().clone(),
+                };
+            }
+            BoundType::LowerBound => {
+                if !NT::PV && tt_score >= beta {
+                    return SearchResult {
+                        best_move: None,
+                        score: tt_score,
+                        pv: Vec::new(),
+                        stats: searcher.stats().clone(),
+                    };
+                }
+            }
+            BoundType::UpperBound => {
+                if !NT::PV && tt_score <= alpha {
+                    return SearchResult {
+                        best_move: None,
+                        score: tt_score,
+                        pv: Vec::new(),
+                        stats: searcher.stats().clone(),
+                    };
+                }
+            }
+            BoundType::None => {}
+        }
+    }
+
     // Stand-pat evaluation using incremental evaluator
     searcher.inc_eval_calls();
     let t_eval = Instant::now();
@@ -61,6 +129,7 @@ pub fn quiescence<NT: NodeType>(
 
     // Beta cutoff: position is already too good
     if stand_pat >= beta {
+        searcher.shared.qtt.store(hash, None, beta.to_tt(ply.raw()), stand_pat, Depth::QS, BoundType::LowerBound);
         return SearchResult {
             best_move: None,
             score: beta,
@@ -94,6 +163,8 @@ pub fn quiescence<NT: NodeType>(
     searcher.add_gen_time(t_gen.elapsed().as_nanos() as u64);
 
     if moves.is_empty() {
+        let bound = qtt_bound(alpha, orig_alpha, beta);
+        searcher.shared.qtt.store(hash, None, alpha.to_tt(ply.raw()), stand_pat, Depth::QS, bound);
         return SearchResult {
             best_move: None,
             score: alpha,
@@ -103,10 +174,11 @@ pub fn quiescence<NT: NodeType>(
     }
 
     let t_order = Instant::now();
-    ordering::order_captures(board, &mut moves);
+    ordering::order_captures(board, &mut moves, tt_move);
     searcher.add_order_time(t_order.elapsed().as_nanos() as u64);
 
     let mut best_score = stand_pat;
+    let mut best_capture = None;
     let mut pv = Vec::new();
 
     for i in 0..moves.len() {
@@ -134,17 +206,21 @@ pub fn quiescence<NT: NodeType>(
             continue;
         }
 
-        let new_board = board.make_move_new(m);
-        
-        // Clone evaluator for next depth and update incrementally
-        let mut child_evaluator = evaluator.clone();
-        child_evaluator.update_move(board, m); // board is position BEFORE move
+        // Make the move in place, update the evaluator incrementally, and
+        // undo both right after the recursive call returns, instead of
+        // heap-cloning a fresh board/evaluator per capture.
+        let board_undo = board.make_move(m);
+        let eval_undo = evaluator.apply_move(&board_undo.board_before(), m, board);
 
-        let result = quiescence::<NT::Next>(searcher, &mut child_evaluator, &new_board, ply.next(), -beta, -alpha);
+        let result = quiescence::<NT::Next>(searcher, evaluator, board, ply.next(), -beta, -alpha);
         let score = -result.score;
 
+        evaluator.revert_move(eval_undo);
+        board.unmake_move(board_undo);
+
         if score > best_score {
             best_score = score;
+            best_capture = Some(m);
 
             pv.clear();
             pv.push(m);
@@ -159,6 +235,11 @@ pub fn quiescence<NT: NodeType>(
         }
     }
 
+    if !searcher.should_stop() {
+        let bound = qtt_bound(best_score, orig_alpha, beta);
+        searcher.shared.qtt.store(hash, best_capture, best_score.to_tt(ply.raw()), stand_pat, Depth::QS, bound);
+    }
+
     SearchResult {
         best_move: None,
         score: best_score,
@@ -0,0 +1,67 @@
+//! Precomputed Late Move Reduction (LMR) table.
+//!
+//! The per-node reduction used to scale `ln(depth) * ln(move_idx)` costs two
+//! `f32::ln` calls and a float divide on every reduced move. Instead,
+//! precompute a single int-scaled `ln` table once (when the thread count is
+//! known) and derive each node's reduction from two table lookups and a
+//! shift, the same trick the table-lookup form of Stockfish's LMR uses.
+
+const TABLE_SIZE: usize = 220;
+/// The product of two lookups is in units of `1 / (1 << SHIFT)` plies, so
+/// `reduction()` brings it back to plies with a single right shift instead
+/// of a divide.
+const SHIFT: u32 = 10;
+/// Base multiplier on `ln(i)`; tuned so `reduction()` lands in the same
+/// ballpark as the old `ln(depth) * ln(move_idx + 1) / 1.9` formula.
+const BASE: f32 = 23.0;
+/// Above this raw reduction, non-PV nodes get bumped one ply further: a
+/// non-PV line is less likely to matter, so lean into a deeper cut once
+/// the base formula already wants a non-trivial reduction.
+const NON_PV_CUTOFF: i32 = 3;
+
+/// Lookup table of `(base + ln(thread_count)) * ln(i)`, int-scaled.
+#[derive(Clone)]
+pub struct Reductions {
+    table: [i32; TABLE_SIZE],
+}
+
+impl Reductions {
+    /// Build the table for a Lazy SMP run of `thread_count` threads. More
+    /// threads already cover the tree more broadly, so each one can afford
+    /// to reduce slightly more.
+    pub fn new(thread_count: usize) -> Self {
+        let thread_term = (thread_count.max(1) as f32).ln();
+        let mut table = [0i32; TABLE_SIZE];
+        for (i, slot) in table.iter_mut().enumerate().skip(1) {
+            *slot = ((BASE + thread_term) * (i as f32).ln()) as i32;
+        }
+        Self { table }
+    }
+
+    #[inline]
+    fn get(&self, i: i32) -> i32 {
+        self.table[(i.max(0) as usize).min(TABLE_SIZE - 1)]
+    }
+
+    /// Reduction (in plies) for the move at `move_idx` (0-based) searched at
+    /// `depth`, given whether this is a PV node and whether the side to
+    /// move is improving.
+    pub fn reduction(&self, depth: i32, move_idx: usize, pv: bool, improving: bool) -> i32 {
+        let raw = self.get(depth) * self.get(move_idx as i32 + 1);
+        let mut r = (raw + (1 << (SHIFT - 1))) >> SHIFT;
+
+        if !pv && r > NON_PV_CUTOFF {
+            r += 1;
+        }
+
+        // Reduce one ply less when improving (the position is trending up,
+        // so late moves are more likely to still matter), one more when not.
+        r + (1 - improving as i32)
+    }
+}
+
+impl Default for Reductions {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
@@ -0,0 +1,181 @@
+//! UCI_Elo-based strength limiting.
+//!
+//! Maps a requested Elo rating onto concrete search handicaps: a shallower
+//! iterative-deepening depth cap, and a configurable chance of playing a
+//! noise-perturbed, softmax-sampled root move instead of the true best one.
+//! None of this touches search correctness at full strength — `Skill` is
+//! only consulted by `Searcher` when `UCI_LimitStrength` is on.
+
+use crate::book::Rng;
+use crate::types::{Depth, Move, Score, MAX_DEPTH};
+
+/// Stockfish's advertised `UCI_Elo` range. Below `MIN_ELO` the handicap
+/// below is already close to its weakest; above `MAX_ELO` it's fully faded
+/// out, equivalent to no limiting at all.
+pub const MIN_ELO: i32 = 1320;
+pub const MAX_ELO: i32 = 3190;
+
+/// Shallowest depth cap, at `MIN_ELO`.
+const MIN_DEPTH_PLIES: i32 = 5;
+
+/// Blunder probability at `MIN_ELO`; fades linearly to 0 at `MAX_ELO`.
+const MAX_BLUNDER_PROBABILITY: f64 = 0.35;
+
+/// Score-noise standard deviation (centipawns) at `MIN_ELO`; fades linearly
+/// to 0 at `MAX_ELO`.
+const MAX_SCORE_NOISE_CP: f64 = 150.0;
+
+/// Softmax temperature (centipawns) used by `pick_move`: fixed rather than
+/// scaled by Elo, so a several-hundred-centipawn gap still reliably picks
+/// the sound move even at the weakest setting — only noise and the
+/// up-front blunder roll should make the engine play badly, not a
+/// temperature so high it picks uniformly at random.
+const SOFTMAX_TEMPERATURE_CP: f64 = 100.0;
+
+/// A target playing strength, derived from a requested `UCI_Elo` value.
+#[derive(Debug, Clone, Copy)]
+pub struct Skill {
+    elo: i32,
+}
+
+impl Skill {
+    pub fn new(elo: i32) -> Self {
+        Self { elo: elo.clamp(MIN_ELO, MAX_ELO) }
+    }
+
+    /// Position within the Elo range: 0.0 at `MIN_ELO`, 1.0 at `MAX_ELO`.
+    fn strength(&self) -> f64 {
+        (self.elo - MIN_ELO) as f64 / (MAX_ELO - MIN_ELO) as f64
+    }
+
+    /// Iterative-deepening depth cap: `MIN_DEPTH_PLIES` at the weakest
+    /// setting, scaling up to the engine's normal `MAX_DEPTH` (i.e.
+    /// effectively uncapped) at the strongest.
+    pub fn depth_cap(&self) -> Depth {
+        let plies = MIN_DEPTH_PLIES
+            + ((MAX_DEPTH - MIN_DEPTH_PLIES) as f64 * self.strength()).round() as i32;
+        Depth::from_plies(plies)
+    }
+
+    /// Chance per move of overriding the true best move with a
+    /// noise-perturbed softmax sample (see `pick_move`).
+    pub fn blunder_probability(&self) -> f64 {
+        MAX_BLUNDER_PROBABILITY * (1.0 - self.strength())
+    }
+
+    /// Standard deviation of the Gaussian noise `pick_move` adds to each
+    /// candidate's score before sampling.
+    pub fn score_noise_stddev(&self) -> f64 {
+        MAX_SCORE_NOISE_CP * (1.0 - self.strength())
+    }
+
+    /// Choose a move from `candidates` (root moves with their true scores,
+    /// best first — e.g. `Searcher`'s MultiPV lines). With probability
+    /// `1 - blunder_probability`, returns the best move unchanged;
+    /// otherwise perturbs every candidate's score with Gaussian noise and
+    /// samples one via softmax, so weaker settings occasionally prefer a
+    /// worse-but-plausible move instead of the best one. Returns `None`
+    /// only if `candidates` is empty.
+    pub fn pick_move(&self, candidates: &[(Move, Score)], rng: &mut Rng) -> Option<Move> {
+        let (&(best_move, _), rest) = candidates.split_first()?;
+        if rest.is_empty() || rng.next_f64() >= self.blunder_probability() {
+            return Some(best_move);
+        }
+
+        let stddev = self.score_noise_stddev();
+        let noisy_scores: Vec<f64> = candidates
+            .iter()
+            .map(|&(_, score)| score.0 as f64 + gaussian_noise(rng, stddev))
+            .collect();
+
+        let max_noisy = noisy_scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let weights: Vec<f64> = noisy_scores
+            .iter()
+            .map(|&s| ((s - max_noisy) / SOFTMAX_TEMPERATURE_CP).exp())
+            .collect();
+        let total: f64 = weights.iter().sum();
+
+        let sample = rng.next_f64() * total;
+        let mut cumulative = 0.0;
+        for (i, w) in weights.iter().enumerate() {
+            cumulative += w;
+            if sample < cumulative {
+                return Some(candidates[i].0);
+            }
+        }
+        // Floating-point rounding can leave `sample` just past the last
+        // cumulative boundary.
+        Some(best_move)
+    }
+}
+
+/// One Box-Muller sample from `N(0, stddev^2)`.
+fn gaussian_noise(rng: &mut Rng, stddev: f64) -> f64 {
+    if stddev <= 0.0 {
+        return 0.0;
+    }
+    let u1 = rng.next_f64().max(f64::MIN_POSITIVE);
+    let u2 = rng.next_f64();
+    let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+    z * stddev
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_depth_cap_is_bounded_by_elo_range() {
+        assert_eq!(Skill::new(MIN_ELO).depth_cap(), Depth::from_plies(MIN_DEPTH_PLIES));
+        assert_eq!(Skill::new(MAX_ELO).depth_cap(), Depth::from_plies(MAX_DEPTH));
+        assert!(Skill::new(2000).depth_cap() > Skill::new(MIN_ELO).depth_cap());
+    }
+
+    #[test]
+    fn test_elo_is_clamped_to_the_advertised_range() {
+        assert_eq!(Skill::new(0).depth_cap(), Skill::new(MIN_ELO).depth_cap());
+        assert_eq!(Skill::new(9999).depth_cap(), Skill::new(MAX_ELO).depth_cap());
+    }
+
+    #[test]
+    fn test_max_elo_never_blunders() {
+        assert_eq!(Skill::new(MAX_ELO).blunder_probability(), 0.0);
+        assert_eq!(Skill::new(MAX_ELO).score_noise_stddev(), 0.0);
+    }
+
+    #[test]
+    fn test_pick_move_is_best_with_a_single_candidate() {
+        let skill = Skill::new(MIN_ELO);
+        let mv = chess::ChessMove::new(chess::Square::E2, chess::Square::E4, None);
+        let mut rng = Rng::new(1);
+        assert_eq!(skill.pick_move(&[(mv, Score::cp(0))], &mut rng), Some(mv));
+    }
+
+    #[test]
+    fn test_pick_move_never_blunders_at_max_elo() {
+        let skill = Skill::new(MAX_ELO);
+        let best = chess::ChessMove::new(chess::Square::E2, chess::Square::E4, None);
+        let other = chess::ChessMove::new(chess::Square::D2, chess::Square::D4, None);
+        let mut rng = Rng::new(2);
+        for _ in 0..50 {
+            let picked = skill.pick_move(&[(best, Score::cp(50)), (other, Score::cp(40))], &mut rng);
+            assert_eq!(picked, Some(best));
+        }
+    }
+
+    #[test]
+    fn test_pick_move_sometimes_blunders_at_min_elo() {
+        let skill = Skill::new(MIN_ELO);
+        let best = chess::ChessMove::new(chess::Square::E2, chess::Square::E4, None);
+        let other = chess::ChessMove::new(chess::Square::D2, chess::Square::D4, None);
+        let mut rng = Rng::new(3);
+        let mut saw_other = false;
+        for _ in 0..200 {
+            if skill.pick_move(&[(best, Score::cp(50)), (other, Score::cp(40))], &mut rng) == Some(other) {
+                saw_other = true;
+                break;
+            }
+        }
+        assert!(saw_other, "expected the weakest setting to occasionally pick a non-best move");
+    }
+}
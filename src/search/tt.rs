@@ -4,13 +4,46 @@
 //! that stores search results to avoid redundant computation.
 //!
 //! # Design
-//! - 8-byte entries packed into AtomicU64 for lock-free access
+//! - Entries packed into a pair of `AtomicU64`s per slot, validated on read
+//!   via Hyatt's lockless XOR scheme (see `TranspositionTable::probe`)
 //! - Depth-preferred replacement with age-based eviction
-//! - Lock-free for Lazy SMP multi-threading support
+//! - Lock-free for Lazy SMP multi-threading support: every search thread
+//!   probes/stores the same table with no locking, so helper threads (see
+//!   `Searcher::search_parallel` and `Searcher::should_skip_depth`) cross-
+//!   pollinate through it as they explore different parts of the tree
 
 use crate::types::{Move, Score, Depth, Hash};
 use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
 
+/// Issue a software prefetch-for-read hint at `ptr`, using the target's
+/// native intrinsic where one exists. On targets with no stable prefetch
+/// intrinsic, fall back to an actual volatile read of the address: it can't
+/// pull the line into cache ahead of time the way a real prefetch can, but
+/// it at least warms the cache for whichever load follows shortly after,
+/// which a pure no-op wouldn't.
+#[inline(always)]
+fn prefetch_read(ptr: *const i8) {
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        std::arch::x86_64::_mm_prefetch(ptr, std::arch::x86_64::_MM_HINT_T0);
+    }
+    #[cfg(target_arch = "x86")]
+    unsafe {
+        std::arch::x86::_mm_prefetch(ptr, std::arch::x86::_MM_HINT_T0);
+    }
+    #[cfg(target_arch = "aarch64")]
+    unsafe {
+        // No stable `core::arch::aarch64` prefetch intrinsic exists yet, so
+        // emit the PRFM instruction directly; `pldl1keep` mirrors
+        // `_MM_HINT_T0` (prefetch for a read, keep in all cache levels).
+        std::arch::asm!("prfm pldl1keep, [{0}]", in(reg) ptr, options(nostack, preserves_flags, readonly));
+    }
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "x86", target_arch = "aarch64")))]
+    unsafe {
+        std::ptr::read_volatile(ptr);
+    }
+}
+
 /// Type of bound stored in TT entry
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -38,20 +71,23 @@ impl From<u8> for BoundType {
 
 /// A single entry in the transposition table.
 ///
-/// Packed into 8 bytes (64 bits) for atomic access:
-/// - key: 16 bits (upper bits of hash for verification)
+/// Packed into the 64-bit `data` word of a `Slot` (see below) — the hash
+/// itself lives outside `TTEntry`, validated via the sibling word instead of
+/// a truncated key field:
 /// - best_move: 16 bits (encoded move)
 /// - score: 16 bits
+/// - static_eval: 16 bits
 /// - depth: 8 bits
 /// - bound_and_age: 8 bits (bound type in low 2 bits, age in high 6 bits)
 #[derive(Debug, Clone, Copy, Default)]
 pub struct TTEntry {
-    /// Upper 16 bits of Zobrist hash for verification
-    key: u16,
     /// Best move found (encoded)
     best_move: u16,
     /// Evaluation score
     score: i16,
+    /// Static (pre-search) evaluation, cached so a TT hit that isn't a
+    /// cutoff can skip recomputing NNUE eval. `Score::none()` if unset.
+    static_eval: i16,
     /// Search depth
     depth: i8,
     /// Bound type (2 bits) + generation/age (6 bits)
@@ -61,51 +97,45 @@ pub struct TTEntry {
 impl TTEntry {
     /// Create a new TT entry
     pub fn new(
-        hash: Hash,
         best_move: Option<Move>,
         score: Score,
+        static_eval: Score,
         depth: Depth,
         bound: BoundType,
         generation: u8,
     ) -> Self {
         Self {
-            key: (hash >> 48) as u16,
             best_move: encode_move(best_move),
             score: score.raw() as i16,
-            depth: depth.raw() as i8,
+            static_eval: static_eval.raw() as i16,
+            depth: depth.to_plies() as i8,
             bound_and_age: (bound as u8) | ((generation & 0x3F) << 2),
         }
     }
-    
-    /// Pack entry into a u64 for atomic storage
-    /// Layout: key(16) | best_move(16) | score(16) | depth(8) | bound_and_age(8)
+
+    /// Pack entry into the `data` word.
+    /// Layout: best_move(16) | score(16) | static_eval(16) | depth(8) | bound_and_age(8)
     #[inline]
     pub fn to_u64(&self) -> u64 {
-        ((self.key as u64) << 48)
-            | ((self.best_move as u64) << 32)
-            | (((self.score as u16) as u64) << 16)
+        ((self.best_move as u64) << 48)
+            | (((self.score as u16) as u64) << 32)
+            | (((self.static_eval as u16) as u64) << 16)
             | ((self.depth as u8 as u64) << 8)
             | (self.bound_and_age as u64)
     }
-    
-    /// Unpack entry from a u64
+
+    /// Unpack entry from a `data` word
     #[inline]
     pub fn from_u64(raw: u64) -> Self {
         Self {
-            key: (raw >> 48) as u16,
-            best_move: (raw >> 32) as u16,
-            score: (raw >> 16) as i16,
+            best_move: (raw >> 48) as u16,
+            score: (raw >> 32) as i16,
+            static_eval: (raw >> 16) as i16,
             depth: (raw >> 8) as i8,
             bound_and_age: raw as u8,
         }
     }
 
-    /// Check if entry matches the given hash
-    #[inline]
-    pub fn matches(&self, hash: Hash) -> bool {
-        self.key == (hash >> 48) as u16
-    }
-
     /// Get the bound type
     #[inline]
     pub fn bound(&self) -> BoundType {
@@ -124,10 +154,16 @@ impl TTEntry {
         Score::cp(self.score as i32)
     }
 
+    /// Get the cached static evaluation, if one was stored.
+    #[inline]
+    pub fn static_eval(&self) -> Score {
+        Score::cp(self.static_eval as i32)
+    }
+
     /// Get the depth
     #[inline]
     pub fn depth(&self) -> Depth {
-        Depth::new(self.depth as i32)
+        Depth::from_plies(self.depth as i32)
     }
 
     /// Get the best move
@@ -188,10 +224,46 @@ fn decode_move(encoded: u16) -> Option<Move> {
     Some(Move::new(from, to, promo))
 }
 
-/// Lock-free Transposition Table using AtomicU64
+/// Entries per cluster. Each entry is now two 8-byte words (see `Slot`), so
+/// 4 entries exactly fill one 64-byte cache line.
+const CLUSTER_SIZE: usize = 4;
+
+/// How strongly an entry's age (in generations since last touched) counts
+/// against it in `store`'s replacement score, relative to depth.
+const GEN_WEIGHT: i32 = 2;
+
+/// `TTEntry::generation` is packed into 6 bits, so it wraps at 64.
+const GEN_MASK: u8 = 0x3F;
+const GENERATION_CYCLE: u8 = 64;
+
+/// One lock-free entry slot, stored as two words per Hyatt's classic
+/// lockless-TT scheme: `data` is the packed `TTEntry`, and `key_xor_data` is
+/// the full Zobrist hash XORed with `data`. A 128-bit entry can't be written
+/// atomically as a unit, so a concurrent writer could interleave with a
+/// reader's two loads; `probe`/`store` detect that by re-deriving the hash
+/// as `key_xor_data ^ data` and rejecting the entry if it doesn't match the
+/// hash being looked up, rather than trusting a possibly-torn read.
+#[derive(Default)]
+struct Slot {
+    key_xor_data: AtomicU64,
+    data: AtomicU64,
+}
+
+/// A cache-line-aligned bucket of `CLUSTER_SIZE` slots sharing one index.
+/// `probe`/`store` scan every slot for a key match instead of coarsely
+/// hashing to a single slot, which raises effective hit rate under Lazy SMP
+/// contention (an index collision no longer evicts outright) while keeping
+/// each access confined to one cache line.
+#[repr(align(64))]
+#[derive(Default)]
+struct Cluster {
+    slots: [Slot; CLUSTER_SIZE],
+}
+
+/// Lock-free Transposition Table of cache-line-aligned clusters.
 pub struct TranspositionTable {
-    /// Table entries as atomic u64 values
-    entries: Vec<AtomicU64>,
+    /// Table entries grouped into cache-line clusters.
+    clusters: Vec<Cluster>,
     /// Current generation (incremented each new search)
     generation: AtomicU8,
     /// Size in MB (for reporting)
@@ -205,19 +277,17 @@ unsafe impl Sync for TranspositionTable {}
 impl TranspositionTable {
     /// Create a new TT with given size in MB
     pub fn new(size_mb: usize) -> Self {
-        // TTEntry is 8 bytes
-        let entry_size = 8;
+        // Each entry is now two 8-byte words (data + key_xor_data)
+        let entry_size = 16;
         let num_entries = (size_mb * 1024 * 1024) / entry_size;
-        // Round to power of 2 for fast modulo
-        let num_entries = num_entries.next_power_of_two() / 2;
-        let num_entries = num_entries.max(1024); // Minimum 1024 entries
+        // Round to power of 2 clusters for fast modulo
+        let num_clusters = (num_entries / CLUSTER_SIZE).next_power_of_two() / 2;
+        let num_clusters = num_clusters.max(256); // Minimum 256 clusters (1024 entries)
 
-        let entries = (0..num_entries)
-            .map(|_| AtomicU64::new(0))
-            .collect();
+        let clusters = (0..num_clusters).map(|_| Cluster::default()).collect();
 
         Self {
-            entries,
+            clusters,
             generation: AtomicU8::new(0),
             size_mb,
         }
@@ -226,20 +296,20 @@ impl TranspositionTable {
     /// Get the number of entries
     #[inline]
     pub fn len(&self) -> usize {
-        self.entries.len()
+        self.clusters.len() * CLUSTER_SIZE
     }
 
     /// Check if table is empty
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.entries.is_empty()
+        self.clusters.is_empty()
     }
 
     /// Get size in MB
     pub fn size_mb(&self) -> usize {
         self.size_mb
     }
-    
+
     /// Get current generation
     #[inline]
     pub fn generation(&self) -> u8 {
@@ -252,64 +322,102 @@ impl TranspositionTable {
         self.generation.fetch_add(1, Ordering::Relaxed);
     }
 
-    /// Get index for a hash
+    /// Get the cluster index for a hash
     #[inline]
     fn index(&self, hash: Hash) -> usize {
         // Fast modulo for power-of-2 size
-        (hash as usize) & (self.entries.len() - 1)
+        (hash as usize) & (self.clusters.len() - 1)
+    }
+
+    /// How stale `entry_gen` is relative to `current_gen`, correctly
+    /// handling wraparound of the 6-bit generation counter.
+    #[inline]
+    fn relative_age(current_gen: u8, entry_gen: u8) -> u8 {
+        (GENERATION_CYCLE.wrapping_add(current_gen).wrapping_sub(entry_gen)) & GEN_MASK
     }
 
     /// Probe the TT for an entry (lock-free)
     #[inline]
     pub fn probe(&self, hash: Hash) -> Option<TTEntry> {
-        let raw = self.entries[self.index(hash)].load(Ordering::Relaxed);
-        if raw == 0 {
-            return None;
-        }
-        
-        let entry = TTEntry::from_u64(raw);
-        if entry.matches(hash) && !entry.is_empty() {
-            Some(entry)
-        } else {
-            None
+        let cluster = &self.clusters[self.index(hash)];
+        for slot in &cluster.slots {
+            let data = slot.data.load(Ordering::Relaxed);
+            let key_xor_data = slot.key_xor_data.load(Ordering::Relaxed);
+            if data == 0 && key_xor_data == 0 {
+                continue;
+            }
+            // Reject a torn read: a concurrent writer updating this slot for
+            // a different hash could be observed mid-write, where neither
+            // word is empty but they don't belong to the same entry.
+            if key_xor_data ^ data != hash {
+                continue;
+            }
+            let entry = TTEntry::from_u64(data);
+            if !entry.is_empty() {
+                return Some(entry);
+            }
         }
+        None
     }
 
     /// Store an entry in the TT (lock-free)
     ///
-    /// Uses depth-preferred replacement with age consideration
-    /// Takes &self - uses atomic operations for thread-safety
+    /// Scans the cluster for a key match or an empty slot first (always
+    /// replaced unconditionally, since an empty slot is free and a key match
+    /// is just fresher information about the same position). Otherwise picks
+    /// the slot minimizing `depth - GEN_WEIGHT * relative_age` as the victim,
+    /// so a deep-but-ancient entry can still be evicted over time.
     pub fn store(
         &self,
         hash: Hash,
         best_move: Option<Move>,
         score: Score,
+        static_eval: Score,
         depth: Depth,
         bound: BoundType,
     ) {
-        let idx = self.index(hash);
-        let existing_raw = self.entries[idx].load(Ordering::Relaxed);
-        let existing = TTEntry::from_u64(existing_raw);
+        let cluster = &self.clusters[self.index(hash)];
         let gen = self.generation();
 
-        // Replacement strategy:
-        // 1. Always replace empty entries
-        // 2. Always replace entries from older generations
-        // 3. Replace if new depth >= existing depth
-        let should_replace = existing.is_empty()
-            || existing.generation() != gen
-            || depth.raw() >= existing.depth.into();
-
-        if should_replace {
-            let new_entry = TTEntry::new(hash, best_move, score, depth, bound, gen);
-            self.entries[idx].store(new_entry.to_u64(), Ordering::Relaxed);
+        let mut victim_slot = 0;
+        let mut victim_value = i32::MAX;
+        let mut replace_slot = None;
+
+        for (i, slot) in cluster.slots.iter().enumerate() {
+            let data = slot.data.load(Ordering::Relaxed);
+            let key_xor_data = slot.key_xor_data.load(Ordering::Relaxed);
+            let is_empty = data == 0 && key_xor_data == 0;
+            let matches_hash = !is_empty && (key_xor_data ^ data) == hash;
+
+            if is_empty || matches_hash {
+                replace_slot = Some(i);
+                break;
+            }
+
+            let existing = TTEntry::from_u64(data);
+            let age = Self::relative_age(gen, existing.generation());
+            let value = existing.depth as i32 - GEN_WEIGHT * age as i32;
+            if value < victim_value {
+                victim_value = value;
+                victim_slot = i;
+            }
         }
+
+        let slot_idx = replace_slot.unwrap_or(victim_slot);
+        let new_entry = TTEntry::new(best_move, score, static_eval, depth, bound, gen);
+        let data = new_entry.to_u64();
+        let slot = &cluster.slots[slot_idx];
+        slot.key_xor_data.store(hash ^ data, Ordering::Relaxed);
+        slot.data.store(data, Ordering::Relaxed);
     }
 
     /// Clear the table
     pub fn clear(&self) {
-        for entry in &self.entries {
-            entry.store(0, Ordering::Relaxed);
+        for cluster in &self.clusters {
+            for slot in &cluster.slots {
+                slot.data.store(0, Ordering::Relaxed);
+                slot.key_xor_data.store(0, Ordering::Relaxed);
+            }
         }
         self.generation.store(0, Ordering::Relaxed);
     }
@@ -317,23 +425,45 @@ impl TranspositionTable {
     /// Get hashfull in permill (for UCI info)
     pub fn hashfull(&self) -> u32 {
         let gen = self.generation();
-        // Sample first 1000 entries
-        let sample_size = self.entries.len().min(1000);
-        let used = self.entries[..sample_size]
-            .iter()
-            .filter(|e| {
-                let entry = TTEntry::from_u64(e.load(Ordering::Relaxed));
+        // Sample the first 1000 entries across clusters
+        let sample: Vec<&Slot> = self.clusters.iter()
+            .flat_map(|c| c.slots.iter())
+            .take(1000)
+            .collect();
+        let sample_size = sample.len();
+        let used = sample.iter()
+            .filter(|slot| {
+                let data = slot.data.load(Ordering::Relaxed);
+                if data == 0 {
+                    return false;
+                }
+                let entry = TTEntry::from_u64(data);
                 !entry.is_empty() && entry.generation() == gen
             })
             .count();
         ((used * 1000) / sample_size) as u32
     }
 
-    /// Prefetch entry for a hash (performance optimization)
+}
+
+/// A table that can be speculatively prefetched by hash before it's actually
+/// probed or stored into, so the caller can hide the entry's load latency
+/// behind whatever work (e.g. `make_move`) happens in between. Mirrors
+/// Pleco's `PreFetchable` trait: a hint only, never a guarantee, so it's
+/// always safe to call speculatively and safe to no-op on targets without a
+/// prefetch intrinsic.
+pub trait PreFetchable {
+    /// Prefetch the slot `hash` maps to.
+    fn prefetch(&self, hash: Hash);
+}
+
+impl PreFetchable for TranspositionTable {
     #[inline]
-    pub fn prefetch(&self, hash: Hash) {
-        let _ = self.index(hash);
-        // Future: use platform-specific prefetch intrinsics
+    fn prefetch(&self, hash: Hash) {
+        // The whole cluster is one cache line, so a single prefetch from its
+        // base address is enough to warm every slot `probe`/`store` will scan.
+        let ptr = self.clusters[self.index(hash)].slots.as_ptr();
+        prefetch_read(ptr as *const i8);
     }
 }
 
@@ -356,11 +486,12 @@ mod tests {
         assert!(tt.probe(hash).is_none());
 
         // Store and retrieve
-        tt.store(hash, None, Score::cp(100), Depth::new(5), BoundType::Exact);
+        tt.store(hash, None, Score::cp(100), Score::cp(80), Depth::from_plies(5), BoundType::Exact);
 
         let entry = tt.probe(hash).expect("Entry should exist");
         assert_eq!(entry.score().raw(), 100);
-        assert_eq!(entry.depth().raw(), 5);
+        assert_eq!(entry.static_eval().raw(), 80);
+        assert_eq!(entry.depth().to_plies(), 5);
         assert_eq!(entry.bound(), BoundType::Exact);
     }
 
@@ -380,21 +511,47 @@ mod tests {
     #[test]
     fn test_entry_pack_unpack() {
         let entry = TTEntry::new(
-            0xABCD123456789000,
             None,
             Score::cp(150),
-            Depth::new(8),
+            Score::cp(120),
+            Depth::from_plies(8),
             BoundType::LowerBound,
             5,
         );
-        
+
         let packed = entry.to_u64();
         let unpacked = TTEntry::from_u64(packed);
-        
-        assert_eq!(entry.key, unpacked.key);
+
         assert_eq!(entry.score, unpacked.score);
+        assert_eq!(entry.static_eval, unpacked.static_eval);
         assert_eq!(entry.depth, unpacked.depth);
         assert_eq!(entry.bound(), unpacked.bound());
         assert_eq!(entry.generation(), unpacked.generation());
     }
+
+    #[test]
+    fn test_prefetch_does_not_disturb_existing_entry() {
+        let tt = TranspositionTable::new(1);
+        let hash: Hash = 0xFEDCBA9876543210;
+        tt.store(hash, None, Score::cp(42), Score::cp(30), Depth::from_plies(3), BoundType::Exact);
+
+        tt.prefetch(hash);
+
+        let entry = tt.probe(hash).expect("Entry should still exist");
+        assert_eq!(entry.score().raw(), 42);
+    }
+
+    #[test]
+    fn test_xor_scheme_rejects_a_hash_mismatch() {
+        // A probe for a different hash that happens to land in the same
+        // cluster (but no slot's derived key matches it) must miss, not
+        // return a neighboring entry's data.
+        let tt = TranspositionTable::new(1);
+        let hash_a: Hash = 0x1111111111111111;
+        let hash_b: Hash = 0x2222222222221111;
+        tt.store(hash_a, None, Score::cp(10), Score::cp(5), Depth::from_plies(2), BoundType::Exact);
+
+        assert!(tt.probe(hash_a).is_some());
+        assert!(tt.probe(hash_b).is_none());
+    }
 }
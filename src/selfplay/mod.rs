@@ -0,0 +1,158 @@
+//! Self-play training-data generation for (re)training the NNUE net.
+//!
+//! Plays engine-vs-engine games from randomized opening plies, and for every
+//! quiet position reached (not in check, and whose chosen move isn't a
+//! capture) appends a `fen | score | result` record to a file: `score` is
+//! the position's search score in centipawns from the side-to-move's
+//! perspective (matching `SearchEvaluator::evaluate`'s convention), and
+//! `result` is the eventual game outcome from that same side's perspective
+//! (`1.0` win, `0.5` draw, `0.0` loss), so a trainer can compare the two
+//! directly. See the `Gen*` UCI options for how a run is configured.
+
+use crate::book::{GameResult, Rng};
+use crate::search::{is_insufficient_material, Searcher, SearchLimits};
+use crate::types::{Board, Color, Move, Piece};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+
+/// Configuration for a `gen` run, read from the `Gen*` UCI options.
+pub struct GenConfig {
+    /// Number of self-play games to generate.
+    pub games: u64,
+    /// Random legal moves applied at the start of each game for opening
+    /// diversity, before the engine starts playing itself.
+    pub random_plies: u32,
+    /// Node budget handed to `SearchLimits` for every move of every game.
+    pub nodes: u64,
+    /// File records are appended to (created if it doesn't exist).
+    pub output_path: String,
+}
+
+/// Plies after which an undecided game is adjudicated a draw, so a pair of
+/// engines that can't make progress against each other doesn't stall a run
+/// indefinitely.
+const MAX_GAME_PLIES: u32 = 200;
+
+/// A quiet position recorded mid-game, waiting on the eventual game result
+/// to become a finished record.
+struct PendingRecord {
+    fen: String,
+    score_cp: i32,
+    side_to_move: Color,
+}
+
+/// Play `config.games` self-play games, appending quiet-position records to
+/// `config.output_path`. `on_progress(games_done, records_written)` is
+/// called after each game completes, so a caller (e.g. the UCI handler) can
+/// report progress on a long run.
+pub fn generate(config: &GenConfig, mut on_progress: impl FnMut(u64, u64)) -> io::Result<u64> {
+    let mut file = OpenOptions::new().create(true).append(true).open(&config.output_path)?;
+    let mut rng = Rng::from_entropy();
+    let mut total_records = 0u64;
+
+    for game in 0..config.games {
+        total_records += play_game(config, &mut rng, &mut file)?;
+        on_progress(game + 1, total_records);
+    }
+
+    Ok(total_records)
+}
+
+/// Play one game to completion, writing its quiet positions (now that the
+/// result is known) to `file`. Returns how many records were written.
+fn play_game(config: &GenConfig, rng: &mut Rng, file: &mut File) -> io::Result<u64> {
+    let mut board = Board::default();
+    let mut history = vec![board.hash()];
+    let mut halfmove_clock: u32 = 0;
+
+    // Random opening plies for diversity between games.
+    for _ in 0..config.random_plies {
+        let moves: Vec<Move> = board.generate_moves().iter().collect();
+        if moves.is_empty() {
+            break;
+        }
+        let mv = moves[rng.next_u64_below(moves.len() as u64) as usize];
+        halfmove_clock = advance_halfmove_clock(&board, mv, halfmove_clock);
+        board = board.make_move_new(mv);
+        history.push(board.hash());
+    }
+
+    let mut searcher = Searcher::new();
+    let mut pending: Vec<PendingRecord> = Vec::new();
+
+    let result = loop {
+        let legal_moves: Vec<Move> = board.generate_moves().iter().collect();
+        if legal_moves.is_empty() {
+            break if board.in_check() {
+                // Side to move is checkmated, so the other side won.
+                match board.side_to_move() {
+                    Color::White => GameResult::BlackWin,
+                    Color::Black => GameResult::WhiteWin,
+                }
+            } else {
+                GameResult::Draw
+            };
+        }
+        if halfmove_clock >= 100
+            || is_insufficient_material(&board)
+            || history.iter().filter(|&&h| h == board.hash()).count() >= 3
+            || history.len() as u32 > MAX_GAME_PLIES
+        {
+            break GameResult::Draw;
+        }
+
+        searcher.set_position_with_history(board, history.clone(), halfmove_clock);
+        let limits = SearchLimits { nodes: Some(config.nodes), ..SearchLimits::new() };
+        let search_result = searcher.search(limits);
+
+        let Some(best) = search_result.best_move else {
+            // No usable move from the search (shouldn't happen with legal
+            // moves available); fall back to the first legal move so the
+            // game can still progress.
+            let mv = legal_moves[0];
+            halfmove_clock = advance_halfmove_clock(&board, mv, halfmove_clock);
+            board = board.make_move_new(mv);
+            history.push(board.hash());
+            continue;
+        };
+
+        // Only quiet positions make good training labels: a position in
+        // check or about to resolve a capture is mid-tactic, not a stable
+        // evaluation target.
+        if !board.in_check() && !best.is_capture() {
+            pending.push(PendingRecord {
+                fen: board.to_string(),
+                score_cp: search_result.score.raw(),
+                side_to_move: board.side_to_move(),
+            });
+        }
+
+        halfmove_clock = advance_halfmove_clock(&board, best, halfmove_clock);
+        board = board.make_move_new(best);
+        history.push(board.hash());
+    };
+
+    for record in &pending {
+        writeln!(file, "{} | {} | {:.1}", record.fen, record.score_cp, result_label(result, record.side_to_move))?;
+    }
+    file.flush()?;
+
+    Ok(pending.len() as u64)
+}
+
+/// The fifty-move-rule halfmove clock after playing `mv` from `board`:
+/// reset on a capture or pawn move, incremented otherwise.
+fn advance_halfmove_clock(board: &Board, mv: Move, halfmove_clock: u32) -> u32 {
+    let resets = mv.is_capture() || board.piece_on(mv.get_source()) == Some(Piece::Pawn);
+    if resets { 0 } else { halfmove_clock + 1 }
+}
+
+/// `result` from `side_to_move`'s perspective: `1.0` if that side went on to
+/// win, `0.0` if it lost, `0.5` for a draw.
+fn result_label(result: GameResult, side_to_move: Color) -> f32 {
+    match (result, side_to_move) {
+        (GameResult::Draw, _) => 0.5,
+        (GameResult::WhiteWin, Color::White) | (GameResult::BlackWin, Color::Black) => 1.0,
+        _ => 0.0,
+    }
+}
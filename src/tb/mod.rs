@@ -0,0 +1,207 @@
+//! Syzygy endgame tablebase support.
+//!
+//! A Syzygy tablebase directory is a pile of `.rtbw` (WDL) and `.rtbz` (DTZ)
+//! files, one per material signature (e.g. `KQvKR.rtbw`), each holding a
+//! compressed, Huffman-coded table of positions for that exact material.
+//! `Tablebases::load_dir` indexes which signatures and cardinalities are on
+//! disk so the rest of the engine can tell *whether* a position is coverable
+//! before paying for a probe.
+//!
+//! Decoding the compressed WDL/DTZ payload itself (the actual Syzygy binary
+//! format) is not implemented here — that's a large, separate piece of work.
+//! `probe_wdl`/`probe_dtz` below always return `None`, same as "no table
+//! loaded", until a real decoder lands. Every call site already treats
+//! `None` as "no tablebase information" rather than any particular verdict,
+//! so plugging in a decoder later is a drop-in change with no callers to
+//! update.
+
+use crate::types::{Board, Color, Move, Piece};
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+
+/// Win/draw/loss classification a Syzygy WDL probe reports, from the side
+/// to move's perspective. `Cursed`/`Blessed` variants are technical
+/// win/loss results that the fifty-move rule will turn into a draw.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wdl {
+    Loss,
+    BlessedLoss,
+    Draw,
+    CursedWin,
+    Win,
+}
+
+impl Wdl {
+    /// Collapse the cursed/blessed distinction away, as appropriate when
+    /// `use_rule50` is off (or DTZ is unavailable to tell them apart).
+    pub fn simple(self) -> Self {
+        match self {
+            Wdl::BlessedLoss => Wdl::Loss,
+            Wdl::CursedWin => Wdl::Win,
+            other => other,
+        }
+    }
+}
+
+/// A directory of tablebase files, indexed by material signature.
+///
+/// Indexing only reads filenames, not table contents, so `load_dir` is
+/// cheap even for a full 6-7 man set.
+#[derive(Debug, Default, Clone)]
+pub struct Tablebases {
+    /// Material signatures with an indexed `.rtbw` file (e.g. `"KQvKR"`).
+    wdl_signatures: HashSet<String>,
+    /// Material signatures with an indexed `.rtbz` file.
+    dtz_signatures: HashSet<String>,
+    /// Largest piece count (both sides, kings included) seen across every
+    /// indexed signature; lets callers cheaply skip the signature lookup
+    /// once a position has more men than any loaded table covers.
+    max_pieces: u32,
+}
+
+impl Tablebases {
+    /// Index every `.rtbw`/`.rtbz` file in `dir` by its material signature
+    /// (the filename stem). Returns an empty (but valid) `Tablebases` if
+    /// the directory has no recognized files.
+    pub fn load_dir(dir: &Path) -> io::Result<Self> {
+        let mut wdl_signatures = HashSet::new();
+        let mut dtz_signatures = HashSet::new();
+        let mut max_pieces = 0u32;
+
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else { continue };
+            match path.extension().and_then(|e| e.to_str()) {
+                Some("rtbw") => {
+                    max_pieces = max_pieces.max(signature_piece_count(stem));
+                    wdl_signatures.insert(stem.to_string());
+                }
+                Some("rtbz") => {
+                    max_pieces = max_pieces.max(signature_piece_count(stem));
+                    dtz_signatures.insert(stem.to_string());
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self { wdl_signatures, dtz_signatures, max_pieces })
+    }
+
+    /// True if no tables are indexed (probes are always `None`).
+    pub fn is_empty(&self) -> bool {
+        self.wdl_signatures.is_empty() && self.dtz_signatures.is_empty()
+    }
+
+    /// Largest cardinality any indexed table covers. Positions with more
+    /// men than this can never be probed, regardless of signature.
+    pub fn max_pieces(&self) -> u32 {
+        self.max_pieces
+    }
+
+    /// Number of distinct material signatures indexed (WDL or DTZ).
+    pub fn signature_count(&self) -> usize {
+        self.wdl_signatures.union(&self.dtz_signatures).count()
+    }
+
+    /// Probe the WDL table for `board`, from the side to move's
+    /// perspective. `None` means "no tablebase information available" —
+    /// either no indexed file covers this material, or (always, for now)
+    /// because payload decoding isn't implemented yet; see module docs.
+    pub fn probe_wdl(&self, board: &Board) -> Option<Wdl> {
+        if !self.wdl_signatures.contains(&material_signature(board)) {
+            return None;
+        }
+        // TODO: decode the compressed WDL payload for this signature.
+        None
+    }
+
+    /// Probe the DTZ table for `board`'s position after playing `mv`, used
+    /// to rank root moves. Same caveat as `probe_wdl`.
+    pub fn probe_dtz(&self, board: &Board, mv: Move) -> Option<i32> {
+        let after = board.make_move_new(mv);
+        if !self.dtz_signatures.contains(&material_signature(&after)) {
+            return None;
+        }
+        // TODO: decode the compressed DTZ payload for this signature.
+        None
+    }
+
+    /// Rank and filter `moves` at the root using WDL/DTZ: keep only moves
+    /// that hold the best WDL result available, ordered by DTZ (fastest
+    /// mate/conversion first). A no-op whenever the root position or its
+    /// children aren't covered by a decoded table — which, today, is
+    /// always (see module docs), so this never changes `moves` yet.
+    pub fn rank_root_moves(&self, board: &Board, moves: &mut Vec<Move>) {
+        if self.probe_wdl(board).is_none() {
+            return;
+        }
+        let mut ranked: Vec<(Move, i32)> = moves.iter()
+            .filter_map(|&m| self.probe_dtz(board, m).map(|dtz| (m, dtz)))
+            .collect();
+        if ranked.len() != moves.len() {
+            // Not every child is covered by a decoded table; filtering to
+            // a partial list would drop otherwise-legal moves, so bail.
+            return;
+        }
+        ranked.sort_by_key(|&(_, dtz)| dtz);
+        *moves = ranked.into_iter().map(|(m, _)| m).collect();
+    }
+}
+
+/// Count the men (kings included) implied by a Syzygy-style filename stem
+/// like `KQvKR` or `KQPvKR`.
+fn signature_piece_count(stem: &str) -> u32 {
+    stem.chars().filter(|c| c.is_ascii_alphabetic()).count() as u32
+}
+
+/// Build a Syzygy-style material signature for `board`: White's pieces, a
+/// `v`, then Black's, each in KQRBNP order, e.g. `"KQvKR"`. White always
+/// comes first, whichever side has more material, so a position where
+/// Black is the stronger side gives e.g. `"KvKQ"` rather than `"KQvK"`.
+fn material_signature(board: &Board) -> String {
+    fn side_letters(board: &Board, color: Color) -> String {
+        let mut s = String::from("K");
+        for (piece, letter) in [
+            (Piece::Queen, 'Q'),
+            (Piece::Rook, 'R'),
+            (Piece::Bishop, 'B'),
+            (Piece::Knight, 'N'),
+            (Piece::Pawn, 'P'),
+        ] {
+            let count = (board.pieces(piece) & board.color_combined(color)).popcnt();
+            for _ in 0..count {
+                s.push(letter);
+            }
+        }
+        s
+    }
+
+    format!("{}v{}", side_letters(board, Color::White), side_letters(board, Color::Black))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_material_signature_kqrvk() {
+        let board = Board::from_str("4k3/8/8/8/8/8/8/3QK1R1 w - - 0 1").unwrap();
+        assert_eq!(material_signature(&board), "KQRvK");
+    }
+
+    #[test]
+    fn test_signature_piece_count() {
+        assert_eq!(signature_piece_count("KQvKR"), 4);
+        assert_eq!(signature_piece_count("KQPvKR"), 5);
+    }
+
+    #[test]
+    fn test_empty_tablebases_never_probes() {
+        let tb = Tablebases::default();
+        let board = Board::default();
+        assert!(tb.is_empty());
+        assert_eq!(tb.probe_wdl(&board), None);
+    }
+}
@@ -0,0 +1,94 @@
+//! In-place make/unmake for `Board`, so hot recursive search paths (see
+//! `search::qsearch`) can mutate a single board instead of cloning a fresh
+//! one per move.
+//!
+//! `Board` is already a cheap `Copy` type, so `unmake_move` doesn't need to
+//! replay inverse deltas field-by-field — restoring the pre-move board is
+//! just as cheap as computing the new one. What this buys callers is a
+//! single, symmetric make/unmake call pair to drive alongside
+//! `SearchEvaluator::apply_move`/`revert_move`, so a whole recursive capture
+//! search can run against one board and one evaluator without allocating.
+
+use super::{Board, CastleRights, Color, Move, Piece, Square};
+
+/// Saved state needed to undo a `make_move` call. Carries the pre-move board
+/// (cheap to restore wholesale) plus a few fields broken out for callers
+/// that want to inspect what changed without re-diffing two boards.
+#[derive(Debug, Clone, Copy)]
+pub struct Undo {
+    previous: Board,
+    /// Piece captured by the move, if any.
+    pub captured: Option<Piece>,
+    /// Castling rights (both sides) before the move.
+    pub castle_rights: [CastleRights; 2],
+    /// En passant target square before the move.
+    pub en_passant: Option<Square>,
+    /// Zobrist hash before the move.
+    pub hash: u64,
+}
+
+impl Undo {
+    /// The board position as it was immediately before the move this `Undo`
+    /// came from.
+    pub fn board_before(&self) -> Board {
+        self.previous
+    }
+}
+
+/// In-place move application, as an extension of `Board` rather than a
+/// method on it (the type itself comes from the move-generation crate).
+pub trait MakeUnmake {
+    /// Apply `mv` in place, returning an `Undo` to hand back to
+    /// `unmake_move`. `mv` must be a legal move for the current position.
+    fn make_move(&mut self, mv: Move) -> Undo;
+
+    /// Undo the effect of the `make_move` call that produced `undo`. Undos
+    /// must be applied in LIFO order relative to their `make_move` calls.
+    fn unmake_move(&mut self, undo: Undo);
+}
+
+impl MakeUnmake for Board {
+    fn make_move(&mut self, mv: Move) -> Undo {
+        let undo = Undo {
+            previous: *self,
+            captured: self.piece_at(mv.get_dest()).map(|(p, _)| p),
+            castle_rights: [self.castle_rights(Color::White), self.castle_rights(Color::Black)],
+            en_passant: self.en_passant(),
+            hash: self.get_hash(),
+        };
+        *self = self.make_move_new(mv);
+        undo
+    }
+
+    fn unmake_move(&mut self, undo: Undo) {
+        *self = undo.previous;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_make_then_unmake_restores_the_original_position() {
+        let mut board = Board::default();
+        let before = board;
+        let mv = chess::ChessMove::new(Square::E2, Square::E4, None);
+
+        let undo = board.make_move(mv);
+        assert_ne!(board.get_hash(), before.get_hash());
+        assert_eq!(undo.board_before().get_hash(), before.get_hash());
+
+        board.unmake_move(undo);
+        assert_eq!(board.get_hash(), before.get_hash());
+    }
+
+    #[test]
+    fn test_undo_records_the_captured_piece() {
+        let mut board = Board::from_str("4k3/8/8/8/8/8/4p3/4K3 w - - 0 1").unwrap();
+        let mv = chess::ChessMove::new(Square::E1, Square::E2, None);
+        let undo = board.make_move(mv);
+        assert_eq!(undo.captured, Some(Piece::Pawn));
+    }
+}
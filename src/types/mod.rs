@@ -12,11 +12,13 @@
 mod score;
 mod depth;
 mod convert;
+mod make_unmake;
 
 // Re-export our custom types
-pub use score::{Score, SCORE_INFINITY, SCORE_MATE, SCORE_DRAW, SCORE_NONE};
+pub use score::{Score, SCORE_INFINITY, SCORE_MATE, SCORE_DRAW, SCORE_NONE, mate_distance_prune};
 pub use depth::{Depth, Ply, MAX_DEPTH, MAX_PLY};
 pub use convert::ToNnue;
+pub use make_unmake::{MakeUnmake, Undo};
 
 // Re-export chess crate types as canonical types
 // This gives us a single source of truth and avoids confusion
@@ -16,6 +16,13 @@ pub const SCORE_DRAW: i32 = 0;
 const SCORE_MATE_IN_MAX: i32 = SCORE_MATE - 1000;
 const SCORE_MATED_IN_MAX: i32 = -SCORE_MATE + 1000;
 
+// Tablebase win/loss bounds, layered just below the true-mate band so a
+// TB-proven win/loss can outrank any heuristic eval without ever being
+// mistaken for a proven mate (see `is_mate`/`is_tb_win`).
+const SCORE_TB: i32 = SCORE_MATE_IN_MAX - 1;
+const SCORE_TB_WIN_IN_MAX: i32 = SCORE_TB - 1000;
+const SCORE_TB_LOSS_IN_MAX: i32 = -SCORE_TB + 1000;
+
 /// A chess engine score.
 ///
 /// Internally stored as centipawns with special encoding for mate scores.
@@ -43,6 +50,18 @@ impl Score {
         Score(-SCORE_MATE + ply)
     }
 
+    /// Create a tablebase win score (TB win in N plies from root)
+    #[inline]
+    pub const fn tb_win_in(ply: i32) -> Self {
+        Score(SCORE_TB - ply)
+    }
+
+    /// Create a tablebase loss score (TB loss in N plies from root)
+    #[inline]
+    pub const fn tb_loss_in(ply: i32) -> Self {
+        Score(-SCORE_TB + ply)
+    }
+
     /// Draw score
     #[inline]
     pub const fn draw() -> Self {
@@ -91,6 +110,26 @@ impl Score {
         self.is_mate() || self.is_mated()
     }
 
+    /// Check if this is a tablebase-proven win. Ranks below a true mate
+    /// score (see `is_mate`) but above any heuristic eval, so TB results
+    /// can't be confused with a proven checkmate.
+    #[inline]
+    pub const fn is_tb_win(self) -> bool {
+        self.0 >= SCORE_TB_WIN_IN_MAX && self.0 < SCORE_MATE_IN_MAX
+    }
+
+    /// Check if this is a tablebase-proven loss (the mirror of `is_tb_win`).
+    #[inline]
+    pub const fn is_tb_loss(self) -> bool {
+        self.0 <= SCORE_TB_LOSS_IN_MAX && self.0 > SCORE_MATED_IN_MAX
+    }
+
+    /// Check if this is any kind of tablebase score
+    #[inline]
+    pub const fn is_tb_score(self) -> bool {
+        self.is_tb_win() || self.is_tb_loss()
+    }
+
     /// Get mate distance in plies (if this is a mate score)
     #[inline]
     pub const fn mate_distance(self) -> Option<i32> {
@@ -103,29 +142,105 @@ impl Score {
         }
     }
 
-    /// Adjust a mate score when storing in TT (relative to current ply)
+    /// Get tablebase distance in plies (if this is a TB win/loss score),
+    /// analogous to `mate_distance`.
+    #[inline]
+    pub const fn tb_distance(self) -> Option<i32> {
+        if self.is_tb_win() {
+            Some(SCORE_TB - self.0)
+        } else if self.is_tb_loss() {
+            Some(self.0 + SCORE_TB)
+        } else {
+            None
+        }
+    }
+
+    /// Upper bound on what can be achieved from `ply`: even a mate delivered
+    /// on the very next move scores `mate_in(ply + 1)`, so no score can
+    /// exceed it. Used by `mate_distance_prune` to tighten `beta`.
+    #[inline]
+    pub const fn mating_bound(ply: i32) -> Self {
+        Self::mate_in(ply + 1)
+    }
+
+    /// Lower bound on what can be achieved from `ply`: the worst case is
+    /// being mated right now, scoring `mated_in(ply)`. Used by
+    /// `mate_distance_prune` to tighten `alpha`.
+    #[inline]
+    pub const fn mated_bound(ply: i32) -> Self {
+        Self::mated_in(ply)
+    }
+
+    /// Adjust a mate or TB score when storing in TT (relative to current ply)
     #[inline]
     pub const fn to_tt(self, ply: i32) -> Self {
-        if self.is_mate() {
+        if self.is_mate() || self.is_tb_win() {
             Score(self.0 + ply)
-        } else if self.is_mated() {
+        } else if self.is_mated() || self.is_tb_loss() {
             Score(self.0 - ply)
         } else {
             self
         }
     }
 
-    /// Adjust a mate score when retrieving from TT
+    /// Adjust a mate or TB score when retrieving from TT
     #[inline]
     pub const fn from_tt(self, ply: i32) -> Self {
-        if self.is_mate() {
+        if self.is_mate() || self.is_tb_win() {
             Score(self.0 - ply)
-        } else if self.is_mated() {
+        } else if self.is_mated() || self.is_tb_loss() {
             Score(self.0 + ply)
         } else {
             self
         }
     }
+
+    /// Format for a UCI `info ... score ...` line: the usual `mate N` /
+    /// `cp X` rendering (see `Display`), with ` lowerbound`/` upperbound`
+    /// appended when `self` is only a fail-high/fail-low bound rather than
+    /// an exact score, so callers don't duplicate the mate/cp branching.
+    pub fn to_uci(self, alpha: Score, beta: Score) -> String {
+        if self >= beta {
+            format!("{} lowerbound", self)
+        } else if self <= alpha {
+            format!("{} upperbound", self)
+        } else {
+            format!("{}", self)
+        }
+    }
+
+    /// Pick the score to keep when aggregating results from several search
+    /// threads or comparing root moves: the larger raw value, preferring
+    /// the shortest mate among wins and the longest getting-mated among
+    /// losses. The layered mate-score encoding (`mate_in`/`mated_in`) makes
+    /// this identical to plain `Ord::max` — a shorter mate is already a
+    /// larger `mate_in` raw value, and a slower loss is already a larger
+    /// `mated_in` raw value — so `prefer` exists only to name that intent
+    /// and stop call sites from reinventing a subtly-wrong bespoke compare.
+    #[inline]
+    pub fn prefer(self, other: Score) -> Score {
+        self.max(other)
+    }
+
+    /// `Ordering` form of `prefer`, for callers that need the comparison
+    /// itself (e.g. `Iterator::max_by`) rather than the winning value.
+    #[inline]
+    pub fn cmp_prefer(self, other: Score) -> std::cmp::Ordering {
+        self.cmp(&other)
+    }
+}
+
+/// Mate distance pruning: tighten `alpha`/`beta` to the best/worst score
+/// reachable from `ply` (see `Score::mating_bound`/`mated_bound`) and report
+/// whether the window has collapsed, signalling an immediate cutoff. A
+/// shorter mate found higher in the tree makes searching this node pointless
+/// even if it too leads to mate, and the symmetric bound applies for being
+/// mated.
+#[inline]
+pub fn mate_distance_prune(alpha: &mut Score, beta: &mut Score, ply: i32) -> bool {
+    *alpha = (*alpha).max(Score::mated_bound(ply));
+    *beta = (*beta).min(Score::mating_bound(ply));
+    *alpha >= *beta
 }
 
 impl Add for Score {
@@ -168,6 +283,9 @@ impl fmt::Display for Score {
             let moves = (self.0 + SCORE_MATE + 1) / 2;
             write!(f, "mate -{}", moves)
         } else {
+            // TB scores print as `cp` (no dedicated UCI form): they outrank
+            // heuristic evals but aren't a proven mate, so reporting them as
+            // a (very large) centipawn value is the honest representation.
             write!(f, "cp {}", self.0)
         }
     }
@@ -203,4 +321,81 @@ mod tests {
         let restored = tt_score.from_tt(2);
         assert_eq!(mate, restored);
     }
+
+    #[test]
+    fn test_tb_scores() {
+        let tb_win = Score::tb_win_in(10);
+        assert!(tb_win.is_tb_win());
+        assert!(!tb_win.is_tb_loss());
+        assert!(!tb_win.is_mate());
+        assert_eq!(tb_win.tb_distance(), Some(10));
+
+        let tb_loss = Score::tb_loss_in(10);
+        assert!(tb_loss.is_tb_loss());
+        assert!(!tb_loss.is_tb_win());
+        assert!(!tb_loss.is_mated());
+        assert_eq!(tb_loss.tb_distance(), Some(10));
+
+        // A proven mate must never be mistaken for a TB score, and vice versa.
+        let mate = Score::mate_in(5);
+        assert!(mate.is_mate());
+        assert!(!mate.is_tb_win());
+        assert!(tb_win < mate);
+    }
+
+    #[test]
+    fn test_tb_tt_adjustment() {
+        let tb_win = Score::tb_win_in(10);
+        let tt_score = tb_win.to_tt(3);
+        let restored = tt_score.from_tt(3);
+        assert_eq!(tb_win, restored);
+    }
+
+    #[test]
+    fn test_mate_distance_prune_cutoff() {
+        // A mate in 1 found higher in the tree makes any deeper node's
+        // window collapse, since nothing here can beat it.
+        let mut alpha = Score::mate_in(1);
+        let mut beta = Score::infinity();
+        assert!(mate_distance_prune(&mut alpha, &mut beta, 5));
+        assert!(alpha >= beta);
+    }
+
+    #[test]
+    fn test_mate_distance_prune_no_cutoff() {
+        let mut alpha = Score::neg_infinity();
+        let mut beta = Score::infinity();
+        assert!(!mate_distance_prune(&mut alpha, &mut beta, 5));
+        assert_eq!(alpha, Score::mated_bound(5));
+        assert_eq!(beta, Score::mating_bound(5));
+    }
+
+    #[test]
+    fn test_to_uci_bounds() {
+        let alpha = Score::cp(-50);
+        let beta = Score::cp(50);
+
+        assert_eq!(Score::cp(0).to_uci(alpha, beta), "cp 0");
+        assert_eq!(Score::cp(50).to_uci(alpha, beta), "cp 50 lowerbound");
+        assert_eq!(Score::cp(-50).to_uci(alpha, beta), "cp -50 upperbound");
+        assert_eq!(Score::mate_in(3).to_uci(alpha, beta), "mate 2 lowerbound");
+    }
+
+    #[test]
+    fn test_prefer_mate_scores() {
+        // Shortest mate wins among winning mate scores.
+        let mate_in_1 = Score::mate_in(1);
+        let mate_in_5 = Score::mate_in(5);
+        assert_eq!(mate_in_1.prefer(mate_in_5), mate_in_1);
+        assert_eq!(mate_in_5.prefer(mate_in_1), mate_in_1);
+
+        // Slowest (longest-delayed) loss wins among losing mate scores.
+        let mated_in_1 = Score::mated_in(1);
+        let mated_in_5 = Score::mated_in(5);
+        assert_eq!(mated_in_1.prefer(mated_in_5), mated_in_5);
+        assert_eq!(mated_in_5.prefer(mated_in_1), mated_in_5);
+
+        // Ordinary magnitude comparison otherwise.
+        assert_eq!(Score::cp(10).prefer(Score::cp(-10)), Score::cp(10));
+    }
 }
@@ -1,13 +1,37 @@
 //! UCI command handler and main loop.
 
 use super::parser::{parse_command, UciCommand};
-use super::{parse_move, format_move, SearchParams, ENGINE_NAME, ENGINE_AUTHOR};
-use crate::types::{Board, Move, Score};
-use crate::search::{Searcher, SearchLimits};
+use super::{options, parse_move, format_move, SearchParams, ENGINE_NAME, ENGINE_AUTHOR};
+use crate::types::{Board, Depth, Move, Piece, Score};
+use crate::search::{Searcher, SearchLimits, SearchResult, SharedState};
 use crate::eval::nnue;
 use crate::book::PolyglotBook;
 use std::io::{self, BufRead, Write};
 use std::str::FromStr;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+/// A `go ponder` search running on a detached thread: `Searcher::search` is
+/// fully synchronous, so pondering is implemented by handing the whole
+/// `Searcher` (via `mem::take`, since it's `Default`) to a background thread
+/// running an infinite search, and reclaiming it (TT, history, etc. all
+/// intact) once that thread is stopped and joined on `ponderhit`, `stop`, or
+/// a ponder miss.
+struct PonderSearch {
+    handle: thread::JoinHandle<(Searcher, SearchResult)>,
+    /// Cloned before the searcher was moved to the thread, so it can still be
+    /// told to stop without needing `&mut Searcher` back.
+    shared: Arc<SharedState>,
+    /// The real time control `go ponder` was given; applied by `ponderhit`
+    /// once the background infinite search is reclaimed.
+    real_limits: SearchLimits,
+    /// When the ponder search was started, so `ponderhit` can graft the real
+    /// search onto this clock (see `Searcher::search_seeded`) instead of
+    /// giving it the full time budget on top of however long it pondered.
+    started_at: Instant,
+}
 
 /// UCI protocol handler
 pub struct UciHandler {
@@ -27,6 +51,31 @@ pub struct UciHandler {
     quit: bool,
     /// Move overhead in milliseconds (safety buffer for time control)
     move_overhead: u64,
+    /// `setoption name Ponder` value, if the GUI ever sends one. Actual
+    /// pondering is driven entirely by `go ponder`/`ponderhit` (see
+    /// `pondering`), not by this flag.
+    ponder: bool,
+    /// Fixed depth override from the `Depth` option (0 = no override, use `go` params)
+    depth_override: Option<Depth>,
+    /// Name of the currently active net, as reported by `option name EvalFile`
+    eval_file: String,
+    /// Whether `UCI_LimitStrength` is on: `cmd_go` weakens the search via
+    /// `Searcher::set_skill` using `uci_elo` instead of playing at full
+    /// strength.
+    limit_strength: bool,
+    /// Target rating for `UCI_Elo`, consulted only while `limit_strength`
+    /// is on.
+    uci_elo: i32,
+    /// The background search started by `go ponder`, if one is in flight.
+    pondering: Option<PonderSearch>,
+    /// `GenGames` - number of self-play games the `gen` command plays.
+    gen_games: u64,
+    /// `GenRandomPlies` - random opening plies applied before each self-play game.
+    gen_random_plies: u32,
+    /// `GenNodes` - node budget per move during self-play.
+    gen_nodes: u64,
+    /// `GenOutputFile` - file `gen` appends its `fen | score | result` records to.
+    gen_output_file: String,
 }
 
 impl Default for UciHandler {
@@ -39,27 +88,23 @@ impl UciHandler {
     pub fn new() -> Self {
         let mut searcher = Searcher::new();
         
-        // Attempt to load NNUE model (look next to executable first, then current dir)
+        // Look for an explicit net file next to the executable or in the
+        // current dir first; if neither exists, fall through to
+        // `resolve_model`'s embedded/download auto-resolution so the engine
+        // still evaluates with NNUE without the user supplying a path.
         let exe_dir_path = std::env::current_exe()
             .ok()
             .and_then(|p| p.parent().map(|d| d.join("network.nnue")));
-        
-        let nnue_path = if let Some(ref p) = exe_dir_path {
-            if p.exists() {
-                println!("info string Found NNUE next to exe: {:?}", p);
-                p.clone()
-            } else {
-                println!("info string NNUE not at exe path: {:?}", p);
-                std::path::PathBuf::from("network.nnue")
-            }
-        } else {
-            println!("info string Could not determine exe path");
-            std::path::PathBuf::from("network.nnue")
-        };
-        
-        match nnue::load_model(nnue_path.to_str().unwrap_or("network.nnue")) {
-            Ok(model) => {
-                println!("info string NNUE loaded: {}", model.desc);
+
+        let explicit_net_path = exe_dir_path
+            .filter(|p| p.exists())
+            .or_else(|| Some(std::path::PathBuf::from("network.nnue")).filter(|p| p.exists()));
+
+        let mut eval_file = options::default_string("EvalFile").to_string();
+        match nnue::resolve_model(explicit_net_path.as_deref().and_then(|p| p.to_str())) {
+            Ok((model, name)) => {
+                println!("info string NNUE loaded: {} ({})", model.desc, name);
+                eval_file = name;
                 searcher.set_nnue(Some(model));
             },
             Err(e) => {
@@ -107,6 +152,16 @@ impl UciHandler {
             debug: false,
             quit: false,
             move_overhead: 10, // Default 10ms
+            ponder: false,
+            depth_override: None,
+            eval_file,
+            limit_strength: false,
+            uci_elo: 1320, // Matches the "UCI_Elo" spin default in `options::OPTIONS`.
+            pondering: None,
+            gen_games: 1,
+            gen_random_plies: 8,
+            gen_nodes: 5000,
+            gen_output_file: options::default_string("GenOutputFile").to_string(),
         }
     }
 
@@ -138,6 +193,13 @@ impl UciHandler {
     }
 
     fn handle_command(&mut self, cmd: UciCommand) {
+        // Any command other than `ponderhit`/`stop` arriving while we're
+        // pondering is a ponder miss: the GUI's opponent didn't play the
+        // predicted move, so silently abort (no `bestmove`) and let the new
+        // command proceed against whatever position it sets up next.
+        if self.pondering.is_some() && !matches!(cmd, UciCommand::PonderHit | UciCommand::Stop) {
+            self.abort_ponder();
+        }
         match cmd {
             UciCommand::Uci => self.cmd_uci(),
             UciCommand::Debug(on) => self.cmd_debug(on),
@@ -151,6 +213,7 @@ impl UciHandler {
             UciCommand::PonderHit => self.cmd_ponderhit(),
             UciCommand::Quit => self.cmd_quit(),
             UciCommand::Display => self.cmd_display(),
+            UciCommand::Gen => self.cmd_gen(),
             UciCommand::Unknown(s) => {
                 if self.debug {
                     eprintln!("Unknown command: {}", s);
@@ -170,13 +233,18 @@ impl UciHandler {
     fn cmd_uci(&self) {
         self.send(&format!("id name {}", ENGINE_NAME));
         self.send(&format!("id author {}", ENGINE_AUTHOR));
-        
-        // Send options
-        self.send("option name Threads type spin default 1 min 1 max 64");
-        self.send("option name MoveOverhead type spin default 10 min 0 max 5000");
-        self.send("option name OwnBook type check default true");
-        self.send("option name BookPath type string default Human.bin");
-        
+
+        for spec in options::OPTIONS {
+            if spec.name.eq_ignore_ascii_case("EvalFile") {
+                // Report whichever net actually ended up loaded (embedded,
+                // downloaded, or explicitly supplied), not the static
+                // fallback filename from the options table.
+                self.send(&format!("option name EvalFile type string default {}", self.eval_file));
+            } else {
+                self.send(&options::format_option(spec));
+            }
+        }
+
         self.send("uciok");
     }
 
@@ -190,17 +258,37 @@ impl UciHandler {
 
     fn cmd_setoption(&mut self, name: &str, value: Option<&str>) {
         match name.to_lowercase().as_str() {
+            "hash" => {
+                if let Some(v) = value {
+                    if let Ok(mb) = v.parse::<i64>() {
+                        self.searcher.set_hash_size(options::clamp_spin("Hash", mb) as usize);
+                    }
+                }
+            }
             "threads" => {
                 if let Some(v) = value {
-                    if let Ok(n) = v.parse::<usize>() {
-                        self.searcher.set_threads(n);
+                    if let Ok(n) = v.parse::<i64>() {
+                        self.searcher.set_threads(options::clamp_spin("Threads", n) as usize);
                     }
                 }
             }
             "moveoverhead" => {
                 if let Some(v) = value {
-                    if let Ok(ms) = v.parse::<u64>() {
-                        self.move_overhead = ms.min(5000);
+                    if let Ok(ms) = v.parse::<i64>() {
+                        self.move_overhead = options::clamp_spin("MoveOverhead", ms) as u64;
+                    }
+                }
+            }
+            "ponder" => {
+                if let Some(v) = value {
+                    self.ponder = v.to_lowercase() == "true";
+                }
+            }
+            "depth" => {
+                if let Some(v) = value {
+                    if let Ok(d) = v.parse::<i64>() {
+                        let d = options::clamp_spin("Depth", d);
+                        self.depth_override = if d > 0 { Some(Depth::from_plies(d as i32)) } else { None };
                     }
                 }
             }
@@ -212,6 +300,103 @@ impl UciHandler {
                     }
                 }
             }
+            "contempt" => {
+                if let Some(v) = value {
+                    if let Ok(c) = v.parse::<i64>() {
+                        self.searcher.set_contempt(options::clamp_spin("Contempt", c) as i32);
+                    }
+                }
+            }
+            "multipv" => {
+                if let Some(v) = value {
+                    if let Ok(n) = v.parse::<i64>() {
+                        self.searcher.set_multi_pv(options::clamp_spin("MultiPV", n) as usize);
+                    }
+                }
+            }
+            "uci_limitstrength" => {
+                if let Some(v) = value {
+                    self.limit_strength = v.to_lowercase() == "true";
+                }
+            }
+            "uci_elo" => {
+                if let Some(v) = value {
+                    if let Ok(elo) = v.parse::<i64>() {
+                        self.uci_elo = options::clamp_spin("UCI_Elo", elo) as i32;
+                    }
+                }
+            }
+            "syzygypath" => {
+                if let Some(v) = value {
+                    if v.is_empty() {
+                        return;
+                    }
+                    match self.searcher.load_tablebases(v) {
+                        Ok(n) => println!("info string Syzygy tablebases indexed: {} signature(s)", n),
+                        Err(e) => println!("info string Failed to load Syzygy path {}: {:?}", v, e),
+                    }
+                }
+            }
+            "syzygyprobedepth" => {
+                if let Some(v) = value {
+                    if let Ok(d) = v.parse::<i64>() {
+                        self.searcher.set_tb_probe_depth(options::clamp_spin("SyzygyProbeDepth", d) as i32);
+                    }
+                }
+            }
+            "syzygy50moverule" => {
+                if let Some(v) = value {
+                    self.searcher.set_tb_use_rule50(v.to_lowercase() == "true");
+                }
+            }
+            "evalfile" => {
+                if let Some(v) = value {
+                    // An empty value (or the literal "<default>", matching
+                    // how Stockfish spells "use the built-in net") asks for
+                    // auto-resolution instead of a specific path.
+                    let explicit = if v.is_empty() || v.eq_ignore_ascii_case("<default>") {
+                        None
+                    } else {
+                        Some(v)
+                    };
+                    match nnue::resolve_model(explicit) {
+                        Ok((model, name)) => {
+                            println!("info string NNUE loaded: {} ({})", model.desc, name);
+                            self.eval_file = name;
+                            self.searcher.set_nnue(Some(model));
+                        }
+                        Err(e) => {
+                            println!("info string Failed to load NNUE {}: {:?}", v, e);
+                        }
+                    }
+                }
+            }
+            "gengames" => {
+                if let Some(v) = value {
+                    if let Ok(n) = v.parse::<i64>() {
+                        self.gen_games = options::clamp_spin("GenGames", n) as u64;
+                    }
+                }
+            }
+            "genrandomplies" => {
+                if let Some(v) = value {
+                    if let Ok(n) = v.parse::<i64>() {
+                        self.gen_random_plies = options::clamp_spin("GenRandomPlies", n) as u32;
+                    }
+                }
+            }
+            "gennodes" => {
+                if let Some(v) = value {
+                    if let Ok(n) = v.parse::<i64>() {
+                        self.gen_nodes = options::clamp_spin("GenNodes", n) as u64;
+                    }
+                }
+            }
+            "genoutputfile" => {
+                if let Some(v) = value {
+                    self.gen_output_file = v.to_string();
+                }
+            }
             "bookpath" => {
                 if let Some(v) = value {
                     self.book_path = v.to_string();
@@ -258,23 +443,37 @@ impl UciHandler {
         let mut history: Vec<u64> = Vec::with_capacity(moves.len() + 1);
         history.push(self.board.get_hash());
 
+        // Fifty-move-rule halfmove clock: resets on captures and pawn moves
+        let mut halfmove_clock: u32 = 0;
+
         // Apply moves
         for move_str in moves {
             if let Some(m) = parse_move(&self.board, move_str) {
+                let resets_clock = m.is_capture() || self.board.piece_on(m.get_source()) == Some(Piece::Pawn);
                 self.board = self.board.make_move_new(m);
                 history.push(self.board.get_hash());
+                halfmove_clock = if resets_clock { 0 } else { halfmove_clock + 1 };
             } else if self.debug {
                 eprintln!("Invalid move: {}", move_str);
             }
         }
-        
+
         // Store history in searcher for repetition detection
-        self.searcher.set_position_with_history(self.board, history);
+        self.searcher.set_position_with_history(self.board, history, halfmove_clock);
     }
 
-    fn cmd_go(&mut self, params: SearchParams) {
-        // Try opening book first (unless infinite or analysis mode)
-        if self.use_own_book && !params.infinite && params.searchmoves.is_empty() {
+    fn cmd_go(&mut self, mut params: SearchParams) {
+        // Resolve the raw "searchmoves" tokens against the current board now
+        // that we actually have one; the parser only sees strings.
+        if !params.searchmoves_str.is_empty() {
+            params.searchmoves = params.searchmoves_str.iter()
+                .filter_map(|s| parse_move(&self.board, s))
+                .collect();
+        }
+
+        // Try opening book first (unless infinite, analysis mode, or
+        // pondering — a ponder search must never emit `bestmove` on its own).
+        if !params.ponder && self.use_own_book && !params.infinite && params.searchmoves.is_empty() {
             if let Some(ref book) = self.book {
                 if let Some(book_move) = book.probe_move(&self.board) {
                     self.send(&format!("info string book move"));
@@ -285,44 +484,140 @@ impl UciHandler {
         }
 
         // Set up search limits with move overhead
-        let limits = SearchLimits::from_params(&params)
+        let mut limits = SearchLimits::from_params(&params)
             .with_move_overhead(self.move_overhead);
-        
-        // Set position and run search
-        self.searcher.set_position(self.board);
+
+        // The `Depth` option forces every search to a fixed depth regardless
+        // of what the GUI asked for.
+        if let Some(d) = self.depth_override {
+            limits.depth = Some(d);
+        }
+
+        // `UCI_LimitStrength`/`UCI_Elo`: weaken the search so GUIs can
+        // request a beatable opponent. `search` consults this to cap depth
+        // and possibly swap in a softmax-sampled alternative to the true
+        // best move.
+        self.searcher.set_skill(self.limit_strength.then_some(self.uci_elo));
+
+        if params.ponder {
+            // The position already set up by `position` is the predicted
+            // reply the GUI wants pondered on; just search it in the
+            // background with no time cutoff until `ponderhit`/a miss/`stop`.
+            self.start_ponder(limits);
+            return;
+        }
+
+        // Position and history were already recorded by cmd_position; run the search.
         let result = self.searcher.search(limits);
+        self.emit_search_result(&result);
+    }
+
+    /// Move `self.searcher` onto a background thread running an infinite
+    /// search, so `cmd_go` can return immediately without emitting
+    /// `bestmove`. `real_limits` is stashed for `cmd_ponderhit` to apply once
+    /// the background search is reclaimed.
+    fn start_ponder(&mut self, real_limits: SearchLimits) {
+        let mut ponder_limits = real_limits.clone();
+        ponder_limits.infinite = true;
+
+        let shared = Arc::clone(&self.searcher.shared);
+        let mut searcher = std::mem::take(&mut self.searcher);
+        let handle = thread::spawn(move || {
+            let result = searcher.search(ponder_limits);
+            (searcher, result)
+        });
+
+        self.pondering = Some(PonderSearch { handle, shared, real_limits, started_at: Instant::now() });
+    }
+
+    /// Stop and reclaim an in-flight ponder search (if any), discarding its
+    /// result. Used for a silent ponder miss; `cmd_ponderhit` and `cmd_stop`
+    /// reclaim it themselves instead since they need the result.
+    fn abort_ponder(&mut self) {
+        if let Some(p) = self.pondering.take() {
+            p.shared.stop.store(true, Ordering::Relaxed);
+            if let Ok((searcher, _)) = p.handle.join() {
+                self.searcher = searcher;
+            }
+        }
+    }
+
+    /// Send the `info ...` / `bestmove ... [ponder ...]` lines for a
+    /// completed search. Shared by the normal synchronous path in `cmd_go`
+    /// and the post-ponder search in `cmd_ponderhit`/`cmd_stop`. When UCI
+    /// `MultiPV` is above 1, emits one ranked `info multipv <i> ...` line per
+    /// line in `Searcher::pv_lines` instead of a single line.
+    fn emit_search_result(&self, result: &SearchResult) {
+        let stats = &result.stats;
+        let lines = self.searcher.pv_lines();
+
+        if self.searcher.multi_pv() > 1 && !lines.is_empty() {
+            for (i, line) in lines.iter().enumerate() {
+                let info = InfoBuilder::new()
+                    .depth(stats.depth.to_plies())
+                    .seldepth(stats.seldepth.raw())
+                    .multipv(i + 1)
+                    .score(line.score, Score::neg_infinity(), Score::infinity())
+                    .nodes(stats.nodes)
+                    .nps(stats.nps())
+                    .time(stats.time_ms)
+                    .pv(&line.pv)
+                    .build();
+                self.send(&info);
+            }
+        } else {
+            let info = InfoBuilder::new()
+                .depth(stats.depth.to_plies())
+                .seldepth(stats.seldepth.raw())
+                .score(result.score, Score::neg_infinity(), Score::infinity())
+                .nodes(stats.nodes)
+                .nps(stats.nps())
+                .time(stats.time_ms)
+                .pv(&result.pv)
+                .build();
+            self.send(&info);
+        }
 
-        // Send info
-        let stats = result.stats;
-        let pv_str: String = result.pv.iter()
-            .map(|m| format_move(*m))
-            .collect::<Vec<_>>()
-            .join(" ");
-
-        self.send(&format!(
-            "info depth {} seldepth {} score {} nodes {} nps {} time {} pv {}",
-            stats.depth.raw(),
-            stats.seldepth.raw(),
-            result.score,
-            stats.nodes,
-            stats.nps(),
-            stats.time_ms,
-            pv_str
-        ));
-
-        // Send best move
         match result.best_move {
-            Some(m) => self.send(&format!("bestmove {}", format_move(m))),
+            Some(m) => {
+                // The predicted opponent reply for the GUI to ponder next,
+                // i.e. the second move of this search's PV.
+                let ponder = result.pv.get(1)
+                    .map(|m2| format!(" ponder {}", format_move(*m2)))
+                    .unwrap_or_default();
+                self.send(&format!("bestmove {}{}", format_move(m), ponder));
+            }
             None => self.send("bestmove 0000"),
         }
     }
 
     fn cmd_stop(&mut self) {
+        // `stop` always yields a `bestmove`, even while pondering (unlike a
+        // silent ponder miss from any other command).
+        if let Some(p) = self.pondering.take() {
+            p.shared.stop.store(true, Ordering::Relaxed);
+            if let Ok((searcher, result)) = p.handle.join() {
+                self.searcher = searcher;
+                self.emit_search_result(&result);
+            }
+            return;
+        }
         self.searcher.stop();
     }
 
     fn cmd_ponderhit(&mut self) {
-        // TODO: Switch from pondering to normal search
+        let Some(p) = self.pondering.take() else { return; };
+        p.shared.stop.store(true, Ordering::Relaxed);
+        if let Ok((searcher, _)) = p.handle.join() {
+            self.searcher = searcher;
+        }
+        // Convert the ponder into a real timed search, reusing the TT and
+        // other state the background search already accumulated, and
+        // grafting the real time budget onto the clock already spent
+        // pondering instead of starting it fresh.
+        let already_elapsed_ms = p.started_at.elapsed().as_millis() as u64;
+        let result = self.searcher.search_seeded(p.real_limits, already_elapsed_ms);
+        self.emit_search_result(&result);
     }
 
     fn cmd_quit(&mut self) {
@@ -335,6 +630,35 @@ impl UciHandler {
         eprintln!("FEN: {}", self.board);
         eprintln!("Side to move: {:?}", self.board.side_to_move());
     }
+
+    /// Non-standard `gen` command: play `GenGames` self-play games (each
+    /// starting with `GenRandomPlies` random moves, each move capped at
+    /// `GenNodes` nodes) and append their quiet-position records to
+    /// `GenOutputFile`. Runs synchronously on the calling thread, reporting
+    /// progress as `info string` lines, since (unlike `go ponder`) there's no
+    /// `bestmove` contract requiring it to run in the background.
+    fn cmd_gen(&mut self) {
+        let config = crate::selfplay::GenConfig {
+            games: self.gen_games,
+            random_plies: self.gen_random_plies,
+            nodes: self.gen_nodes,
+            output_path: self.gen_output_file.clone(),
+        };
+
+        let send = |msg: &str| {
+            println!("{}", msg);
+            io::stdout().flush().ok();
+        };
+
+        let result = crate::selfplay::generate(&config, |games_done, records| {
+            send(&format!("info string gen {}/{} games, {} records", games_done, config.games, records));
+        });
+
+        match result {
+            Ok(total) => send(&format!("info string gen done, {} records written to {}", total, self.gen_output_file)),
+            Err(e) => send(&format!("info string gen failed: {}", e)),
+        }
+    }
 }
 
 /// Info message builder for search output
@@ -359,8 +683,18 @@ impl InfoBuilder {
         self
     }
 
-    pub fn score(mut self, s: Score) -> Self {
-        self.parts.push(format!("score {}", s));
+    /// 1-indexed rank of this line among the UCI `MultiPV` lines being
+    /// reported (best line is `1`).
+    pub fn multipv(mut self, i: usize) -> Self {
+        self.parts.push(format!("multipv {}", i));
+        self
+    }
+
+    /// Appends `lowerbound`/`upperbound` when `s` is only a fail-high/low
+    /// bound against `(alpha, beta)` rather than an exact score (see
+    /// `Score::to_uci`).
+    pub fn score(mut self, s: Score, alpha: Score, beta: Score) -> Self {
+        self.parts.push(format!("score {}", s.to_uci(alpha, beta)));
         self
     }
 
@@ -5,6 +5,7 @@
 
 mod parser;
 mod handler;
+mod options;
 
 pub use handler::UciHandler;
 
@@ -38,6 +39,9 @@ pub struct SearchParams {
     pub ponder: bool,
     /// Only search these moves
     pub searchmoves: Vec<Move>,
+    /// Raw `searchmoves` tokens as received, before the board is known.
+    /// Resolved into `searchmoves` by `cmd_go` once it has `self.board`.
+    pub searchmoves_str: Vec<String>,
     /// Search for mate in N moves
     pub mate: Option<u32>,
     /// Maximum nodes to search
@@ -52,7 +56,7 @@ impl SearchParams {
     /// Create params for a fixed depth search
     pub fn fixed_depth(depth: i32) -> Self {
         Self {
-            depth: Some(Depth::new(depth)),
+            depth: Some(Depth::from_plies(depth)),
             ..Default::default()
         }
     }
@@ -0,0 +1,83 @@
+//! UCI options registry.
+//!
+//! A typed table of every option this engine advertises. `UciHandler` walks
+//! it to print the `option name ... type ...` lines between `id` and
+//! `uciok`, and uses it to validate/clamp incoming `setoption` values before
+//! `cmd_setoption` dispatches them to whatever state they actually control.
+
+/// Declared type and bounds for a single UCI option.
+pub enum OptionKind {
+    Spin { default: i64, min: i64, max: i64 },
+    Check { default: bool },
+    String { default: &'static str },
+}
+
+/// A single advertised option.
+pub struct OptionSpec {
+    pub name: &'static str,
+    pub kind: OptionKind,
+}
+
+/// All options this engine advertises, in the order sent to the GUI.
+pub const OPTIONS: &[OptionSpec] = &[
+    OptionSpec { name: "Hash", kind: OptionKind::Spin { default: 16, min: 1, max: 4096 } },
+    OptionSpec { name: "Threads", kind: OptionKind::Spin { default: 1, min: 1, max: 64 } },
+    OptionSpec { name: "MoveOverhead", kind: OptionKind::Spin { default: 10, min: 0, max: 5000 } },
+    OptionSpec { name: "Ponder", kind: OptionKind::Check { default: false } },
+    OptionSpec { name: "Depth", kind: OptionKind::Spin { default: 0, min: 0, max: 256 } },
+    OptionSpec { name: "Contempt", kind: OptionKind::Spin { default: 10, min: -100, max: 100 } },
+    OptionSpec { name: "MultiPV", kind: OptionKind::Spin { default: 1, min: 1, max: 256 } },
+    OptionSpec { name: "OwnBook", kind: OptionKind::Check { default: true } },
+    OptionSpec { name: "BookPath", kind: OptionKind::String { default: "Human.bin" } },
+    OptionSpec { name: "EvalFile", kind: OptionKind::String { default: "network.nnue" } },
+    OptionSpec { name: "SyzygyPath", kind: OptionKind::String { default: "" } },
+    OptionSpec { name: "SyzygyProbeDepth", kind: OptionKind::Spin { default: 4, min: 0, max: 100 } },
+    OptionSpec { name: "Syzygy50MoveRule", kind: OptionKind::Check { default: true } },
+    OptionSpec { name: "UCI_LimitStrength", kind: OptionKind::Check { default: false } },
+    OptionSpec { name: "UCI_Elo", kind: OptionKind::Spin { default: 1320, min: 1320, max: 3190 } },
+    OptionSpec { name: "GenGames", kind: OptionKind::Spin { default: 1, min: 1, max: 10_000_000 } },
+    OptionSpec { name: "GenRandomPlies", kind: OptionKind::Spin { default: 8, min: 0, max: 100 } },
+    OptionSpec { name: "GenNodes", kind: OptionKind::Spin { default: 5000, min: 1, max: 100_000_000 } },
+    OptionSpec { name: "GenOutputFile", kind: OptionKind::String { default: "selfplay.txt" } },
+];
+
+/// Format an option as a UCI `option name ... type ...` response line.
+pub fn format_option(spec: &OptionSpec) -> String {
+    match spec.kind {
+        OptionKind::Spin { default, min, max } => {
+            format!("option name {} type spin default {} min {} max {}", spec.name, default, min, max)
+        }
+        OptionKind::Check { default } => {
+            format!("option name {} type check default {}", spec.name, default)
+        }
+        OptionKind::String { default } => {
+            format!("option name {} type string default {}", spec.name, default)
+        }
+    }
+}
+
+/// Look up the static default for a declared `String` option. Returns `""`
+/// for an unknown name or one that isn't a string option.
+pub fn default_string(name: &str) -> &'static str {
+    for spec in OPTIONS {
+        if spec.name.eq_ignore_ascii_case(name) {
+            if let OptionKind::String { default } = spec.kind {
+                return default;
+            }
+        }
+    }
+    ""
+}
+
+/// Clamp a parsed spin value to its declared range. Options that aren't a
+/// known spin (unknown name, or a check/string option) pass through unchanged.
+pub fn clamp_spin(name: &str, value: i64) -> i64 {
+    for spec in OPTIONS {
+        if spec.name.eq_ignore_ascii_case(name) {
+            if let OptionKind::Spin { min, max, .. } = spec.kind {
+                return value.clamp(min, max);
+            }
+        }
+    }
+    value
+}
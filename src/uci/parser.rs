@@ -30,6 +30,8 @@ pub enum UciCommand {
     Quit,
     /// "d" - Debug: display board (non-standard but common)
     Display,
+    /// "gen" - Generate self-play training data (non-standard, see `Gen*` options)
+    Gen,
     /// Unknown command
     Unknown(String),
 }
@@ -55,6 +57,7 @@ pub fn parse_command(input: &str) -> UciCommand {
         Some("ponderhit") => UciCommand::PonderHit,
         Some("quit") => UciCommand::Quit,
         Some("d") => UciCommand::Display,
+        Some("gen") => UciCommand::Gen,
         _ => UciCommand::Unknown(input.to_string()),
     }
 }
@@ -148,7 +151,7 @@ fn parse_go<'a>(parts: &mut impl Iterator<Item = &'a str>) -> UciCommand {
                 i += 1;
                 if i < tokens.len() {
                     if let Ok(d) = tokens[i].parse::<i32>() {
-                        params.depth = Some(Depth::new(d));
+                        params.depth = Some(Depth::from_plies(d));
                     }
                 }
             }
@@ -201,11 +204,11 @@ fn parse_go<'a>(parts: &mut impl Iterator<Item = &'a str>) -> UciCommand {
                 }
             }
             "searchmoves" => {
-                // Remaining tokens are moves
-                // We'll parse them later when we have the board
+                // Remaining tokens are moves in UCI notation; resolved to
+                // `Move`s later, once `cmd_go` has the board to parse against.
                 i += 1;
                 while i < tokens.len() {
-                    // Store as strings for now, will be parsed with board context
+                    params.searchmoves_str.push(tokens[i].to_string());
                     i += 1;
                 }
             }